@@ -0,0 +1,71 @@
+//! Throughput of `parse` for typical streams, run with `cargo bench`.
+//!
+//! Uses `std::time::Instant` instead of a benchmark harness, so the numbers are only
+//! comparable between runs on the same machine.
+
+use std::time::{Duration, Instant};
+
+use midi_stream_parser::MidiStreamParser;
+
+/// Number of bytes fed per measurement.
+const STREAM_LEN: usize = 1 << 20;
+
+/// Number of measurements, the fastest one is reported.
+const RUNS: usize = 10;
+
+/// Returns note on messages using running status.
+fn running_status_notes() -> Vec<u8> {
+    let mut stream = vec![0x90];
+    let mut key = 0u8;
+
+    while stream.len() < STREAM_LEN {
+        key = (key + 1) & 0x7F;
+        stream.extend_from_slice(&[key, 100]);
+    }
+
+    stream
+}
+
+/// Returns SysEx messages filling the parser buffer.
+fn large_sysex<const SYSEX_MAX_LEN: usize>() -> Vec<u8> {
+    let mut stream = Vec::new();
+
+    while stream.len() < STREAM_LEN {
+        stream.push(0xF0);
+        stream.extend((0..SYSEX_MAX_LEN - 2).map(|index| index as u8 & 0x7F));
+        stream.push(0xF7);
+    }
+
+    stream
+}
+
+/// Parse the stream and print the throughput of the fastest run.
+fn measure<const SYSEX_MAX_LEN: usize>(name: &str, stream: &[u8]) {
+    let mut fastest = Duration::MAX;
+    let mut emitted = 0;
+
+    for _ in 0..RUNS {
+        let mut parser = MidiStreamParser::<SYSEX_MAX_LEN>::new();
+        let start = Instant::now();
+        for &byte in stream {
+            // Using the result keeps the parsing from being optimized away.
+            if let Ok(Some(message)) = parser.parse(byte) {
+                emitted += message.len();
+            }
+        }
+        fastest = fastest.min(start.elapsed());
+    }
+
+    let rate = stream.len() as f64 / fastest.as_secs_f64() / 1e6;
+    println!(
+        "{:<24} {:>8.1} MB/s ({} bytes emitted)",
+        name,
+        rate,
+        emitted / RUNS
+    );
+}
+
+fn main() {
+    measure::<256>("running status notes", &running_status_notes());
+    measure::<4096>("large sysex", &large_sysex::<4096>());
+}
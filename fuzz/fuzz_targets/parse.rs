@@ -0,0 +1,53 @@
+//! Feed arbitrary bytes into parsers of different buffer sizes.
+//! The first two bytes select the configuration options, the remaining bytes are parsed.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use midi_stream_parser::{MidiStreamParser, ParserConfig};
+
+/// Returns the configuration with the options selected by the bits of `flags`.
+fn config(flags: u16) -> ParserConfig {
+    ParserConfig {
+        strict: flags & 0x01 != 0,
+        sysex_ring_buffer: flags & 0x02 != 0,
+        truncate_sysex_on_overflow: flags & 0x04 != 0,
+        reset_on_error: flags & 0x08 != 0,
+        preserve_running_status_across_sysex: flags & 0x10 != 0,
+        emit_sysex_start: flags & 0x20 != 0,
+        drop_until_first_status: flags & 0x40 != 0,
+        spec_compliant: flags & 0x80 != 0,
+        sysex_device_id: if flags & 0x100 != 0 { Some(0x10) } else { None },
+        sysex_warn_threshold: 3,
+        ..ParserConfig::new()
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 2 {
+        return;
+    }
+    let (flags, data) = data.split_at(2);
+    let flags = u16::from_le_bytes([flags[0], flags[1]]);
+
+    let mut small = MidiStreamParser::<4>::with_config(config(flags));
+    let mut large = MidiStreamParser::<256>::with_config(config(flags));
+    let mut chunk = [0; 8];
+
+    for byte in data {
+        let _ = small.parse(*byte);
+        small.check_invariants();
+        let _ = large.parse(*byte);
+        large.check_invariants();
+
+        if flags & 0x02 != 0 {
+            // Drain the ring buffers now and then.
+            if *byte & 0x07 == 0 {
+                small.take_sysex_chunk(&mut chunk);
+                small.check_invariants();
+                large.take_sysex_chunk(&mut chunk);
+                large.check_invariants();
+            }
+        }
+    }
+});
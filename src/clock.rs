@@ -0,0 +1,109 @@
+//! Helpers for processing MIDI clock messages.
+
+/// Timing clock message.
+const TIMING_CLOCK: u8 = 0xF8;
+
+/// Start message.
+const START: u8 = 0xFA;
+
+/// Stop message.
+const STOP: u8 = 0xFC;
+
+/// Divider counting timing clock messages and emitting ticks at musical boundaries.
+/// MIDI clock runs at 24 clocks per quarter note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClockDivider {
+    /// Number of clocks per tick.
+    division: u8,
+
+    /// Number of clocks received since the last tick.
+    count: u8,
+}
+
+impl ClockDivider {
+    /// Division for one tick per quarter note.
+    pub const QUARTER_NOTE: u8 = 24;
+
+    /// Division for one tick per eighth note.
+    pub const EIGHTH_NOTE: u8 = 12;
+
+    /// Division for one tick per sixteenth note.
+    pub const SIXTEENTH_NOTE: u8 = 6;
+
+    /// Returns a new divider emitting a tick every `division` clocks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `division` is 0.
+    pub fn new(division: u8) -> Self {
+        assert!(division > 0, "division must not be 0");
+        Self { division, count: 0 }
+    }
+
+    /// Returns the number of clocks per tick.
+    pub fn division(&self) -> u8 {
+        self.division
+    }
+
+    /// Feed a system realtime status byte into the divider and return if a tick occurred.
+    /// Start and stop messages reset the count, other messages are ignored.
+    pub fn feed(&mut self, status: u8) -> bool {
+        match status {
+            TIMING_CLOCK => {
+                self.count += 1;
+                if self.count == self.division {
+                    self.count = 0;
+                    return true;
+                }
+            }
+            START | STOP => {
+                self.count = 0;
+            }
+            _ => {}
+        }
+
+        false
+    }
+}
+
+/// Tracker of the clock phase within the current quarter note.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ClockTracker {
+    /// Number of clocks received since the start of the quarter note.
+    phase: u8,
+}
+
+impl ClockTracker {
+    /// Returns a new tracker at the start of a quarter note.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a system realtime status byte into the tracker.
+    /// Start messages reset the phase, timing clock messages advance it,
+    /// other messages are ignored.
+    pub fn feed(&mut self, status: u8) {
+        match status {
+            TIMING_CLOCK => {
+                self.phase = (self.phase + 1) % ClockDivider::QUARTER_NOTE;
+            }
+            START => {
+                self.phase = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the number of clocks since the start of the quarter note in the range 0-23.
+    pub fn phase_24(&self) -> u8 {
+        self.phase
+    }
+
+    /// Returns the position within the quarter note in the range 0.0 to less than 1.0.
+    pub fn phase_fraction(&self) -> f32 {
+        self.phase as f32 / ClockDivider::QUARTER_NOTE as f32
+    }
+}
+
+#[cfg(test)]
+mod tests;
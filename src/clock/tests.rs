@@ -0,0 +1,54 @@
+//! Unit tests
+
+use super::*;
+
+/// 48 clocks at quarter note division.
+#[test]
+fn quarter_note_ticks() {
+    let mut divider = ClockDivider::new(ClockDivider::QUARTER_NOTE);
+
+    let ticks: Vec<usize> = (0..48).filter(|_| divider.feed(TIMING_CLOCK)).collect();
+
+    assert_eq!(ticks, [23, 47]);
+}
+
+/// Start and stop restart the count.
+#[test]
+fn reset_on_start_stop() {
+    let mut divider = ClockDivider::new(ClockDivider::SIXTEENTH_NOTE);
+
+    for _ in 0..5 {
+        assert!(!divider.feed(TIMING_CLOCK));
+    }
+    assert!(!divider.feed(STOP));
+    assert!(!divider.feed(START));
+    for _ in 0..5 {
+        assert!(!divider.feed(TIMING_CLOCK));
+    }
+    assert!(divider.feed(TIMING_CLOCK));
+}
+
+/// Phase wrapping at 24 clocks and reset by start.
+#[test]
+fn clock_phase() {
+    let mut tracker = ClockTracker::new();
+
+    for _ in 0..6 {
+        tracker.feed(TIMING_CLOCK);
+    }
+    assert_eq!(tracker.phase_24(), 6);
+    assert_eq!(tracker.phase_fraction(), 0.25);
+
+    for _ in 0..18 {
+        tracker.feed(TIMING_CLOCK);
+    }
+    assert_eq!(tracker.phase_24(), 0);
+
+    tracker.feed(TIMING_CLOCK);
+    tracker.feed(STOP);
+    assert_eq!(tracker.phase_24(), 1);
+
+    tracker.feed(START);
+    assert_eq!(tracker.phase_24(), 0);
+    assert_eq!(tracker.phase_fraction(), 0.0);
+}
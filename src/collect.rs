@@ -0,0 +1,115 @@
+//! Collection of parsed messages from byte iterators.
+
+use alloc::vec::Vec;
+
+use crate::{MidiMessage, MidiStreamParser, ParserConfig, SinkError, SysExSink};
+
+/// Extension for byte iterators collecting the complete messages.
+pub trait CollectMidi: Iterator<Item = u8> + Sized {
+    /// Feed all bytes into a parser and collect the complete messages.
+    ///
+    /// SysEx messages are not limited in length. Bytes that can't be assigned to a message
+    /// are skipped, incomplete messages at the end are dropped. Messages are parsed
+    /// following the specification, see [`ParserConfig::spec_compliant`].
+    ///
+    /// ```
+    /// use midi_stream_parser::{CollectMidi, MidiMessage};
+    ///
+    /// let bytes = [0x90, 60, 100, 61, 90, 0xF0, 0x41, 0xF7];
+    /// let messages = bytes.iter().copied().collect_midi();
+    /// let messages: Vec<MidiMessage> = messages.iter().collect();
+    ///
+    /// assert_eq!(messages.len(), 3);
+    /// assert_eq!(messages[2], MidiMessage::SysEx(&[0xF0, 0x41, 0xF7]));
+    /// ```
+    fn collect_midi(self) -> MidiMessages {
+        let mut parser = MidiStreamParser::<0>::with_config(ParserConfig {
+            spec_compliant: true,
+            ..ParserConfig::new()
+        });
+        let mut collector = Collector {
+            messages: MidiMessages::default(),
+            sysex: Vec::new(),
+        };
+
+        for byte in self {
+            if byte == 0xF0 {
+                collector.sysex.clear();
+                collector.sysex.push(byte);
+            }
+            if let Ok(Some(message)) = parser.parse_to_sink(byte, &mut collector) {
+                collector.messages.push(message);
+            }
+        }
+
+        collector.messages
+    }
+}
+
+impl<I: Iterator<Item = u8>> CollectMidi for I {}
+
+/// Messages returned by [`CollectMidi::collect_midi`], stored in a single buffer.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MidiMessages {
+    /// Bytes of all messages.
+    bytes: Vec<u8>,
+
+    /// End index of each message in the buffer.
+    ends: Vec<usize>,
+}
+
+impl MidiMessages {
+    /// Returns the number of messages.
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns `true` if there are no messages.
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the message at `index`.
+    pub fn get(&self, index: usize) -> Option<MidiMessage<'_>> {
+        let end = *self.ends.get(index)?;
+        let start = if index > 0 { self.ends[index - 1] } else { 0 };
+
+        Some(MidiMessage::decode(&self.bytes[start..end]))
+    }
+
+    /// Returns an iterator over the messages.
+    pub fn iter(&self) -> impl Iterator<Item = MidiMessage<'_>> + '_ {
+        (0..self.len()).filter_map(move |index| self.get(index))
+    }
+
+    /// Append the bytes of a complete message.
+    fn push(&mut self, message: &[u8]) {
+        self.bytes.extend_from_slice(message);
+        self.ends.push(self.bytes.len());
+    }
+}
+
+/// Sink assembling SysEx messages next to the collected messages.
+struct Collector {
+    /// Messages collected so far.
+    messages: MidiMessages,
+
+    /// SysEx message in progress.
+    sysex: Vec<u8>,
+}
+
+impl SysExSink for Collector {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), SinkError> {
+        self.sysex.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), SinkError> {
+        self.sysex.push(0xF7);
+        self.messages.push(&self.sysex);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,47 @@
+//! Unit tests
+
+use super::*;
+use crate::SystemRealtimeMessage;
+
+/// Messages collected from a mixed stream with a realtime message inside SysEx.
+#[test]
+fn collect_mixed() {
+    let bytes = [
+        0x10, 0xB0, 7, 90, 8, 60, 0xF0, 0x41, 0xF8, 0x10, 0xF7, 0xC0, 5, 0x90, 60,
+    ];
+
+    let messages = bytes.iter().copied().collect_midi();
+
+    assert_eq!(messages.len(), 5);
+    assert_eq!(
+        messages.iter().collect::<Vec<_>>(),
+        [
+            MidiMessage::ControlChange {
+                channel: 0,
+                controller: 7,
+                value: 90
+            },
+            MidiMessage::ControlChange {
+                channel: 0,
+                controller: 8,
+                value: 60
+            },
+            MidiMessage::SystemRealtime(SystemRealtimeMessage::TimingClock),
+            MidiMessage::SysEx(&[0xF0, 0x41, 0x10, 0xF7]),
+            MidiMessage::ProgramChange {
+                channel: 0,
+                program: 5
+            },
+        ]
+    );
+    assert_eq!(messages.get(5), None);
+}
+
+/// No messages from an empty iterator.
+#[test]
+fn collect_empty() {
+    let messages = core::iter::empty().collect_midi();
+
+    assert!(messages.is_empty());
+    assert_eq!(messages.iter().count(), 0);
+}
@@ -0,0 +1,89 @@
+//! Mirror of the current controller values, e.g. for resending them on a new connection.
+
+use crate::{ChannelModeMessage, MidiMessage};
+
+/// Number of controllers tracked per channel, channel mode controllers 120-127 excluded.
+const CONTROLLERS: usize = 120;
+
+/// Marker for controllers without received value.
+const UNSET: u8 = 0xFF;
+
+/// Last values of the control changes per channel, excluding channel mode messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControllerState {
+    /// Values per channel and controller, `UNSET` if not received.
+    values: [[u8; CONTROLLERS]; 16],
+}
+
+impl Default for ControllerState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ControllerState {
+    /// Returns a new state without any values.
+    pub fn new() -> Self {
+        Self {
+            values: [[UNSET; CONTROLLERS]; 16],
+        }
+    }
+
+    /// Process a message, recording the value of a control change.
+    /// Reset all controllers clears the values of its channel, other messages are ignored.
+    pub fn process(&mut self, message: &MidiMessage) {
+        if let Some((channel, ChannelModeMessage::ResetAllControllers)) = message.channel_mode() {
+            self.values[channel as usize & 0x0F] = [UNSET; CONTROLLERS];
+            return;
+        }
+
+        if let MidiMessage::ControlChange {
+            channel,
+            controller,
+            value,
+        } = *message
+        {
+            if (controller as usize) < CONTROLLERS && value <= 0x7F {
+                self.values[channel as usize & 0x0F][controller as usize] = value;
+            }
+        }
+    }
+
+    /// Returns the last value of a controller on a channel in the range 0-15.
+    pub fn value(&self, channel: u8, controller: u8) -> Option<u8> {
+        let value = *self
+            .values
+            .get(channel as usize)?
+            .get(controller as usize)?;
+
+        if value == UNSET {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Returns an iterator over the control changes restoring the current state,
+    /// ordered by channel and controller number.
+    pub fn messages(&self) -> impl Iterator<Item = MidiMessage<'static>> + '_ {
+        self.values.iter().zip(0..).flat_map(|(values, channel)| {
+            values
+                .iter()
+                .zip(0..)
+                .filter(|(value, _)| **value != UNSET)
+                .map(move |(value, controller)| MidiMessage::ControlChange {
+                    channel,
+                    controller,
+                    value: *value,
+                })
+        })
+    }
+
+    /// Clear all values.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests;
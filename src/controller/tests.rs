@@ -0,0 +1,64 @@
+//! Unit tests
+
+use super::*;
+
+/// Control change on a channel.
+fn control_change(channel: u8, controller: u8, value: u8) -> MidiMessage<'static> {
+    MidiMessage::ControlChange {
+        channel,
+        controller,
+        value,
+    }
+}
+
+/// State dumped after setting a few controllers.
+#[test]
+fn dump_state() {
+    let mut state = ControllerState::new();
+
+    for message in [
+        control_change(3, 10, 64),
+        control_change(0, 7, 100),
+        control_change(0, 7, 90),
+        control_change(0, 123, 0),
+        MidiMessage::NoteOn {
+            channel: 0,
+            note: 60,
+            velocity: 100,
+        },
+        control_change(15, 1, 5),
+    ] {
+        state.process(&message);
+    }
+
+    assert_eq!(state.value(0, 7), Some(90));
+    assert_eq!(state.value(0, 8), None);
+    assert_eq!(state.value(0, 123), None);
+    assert_eq!(state.value(16, 7), None);
+
+    let messages: Vec<_> = state.messages().collect();
+    assert_eq!(
+        messages,
+        [
+            control_change(0, 7, 90),
+            control_change(3, 10, 64),
+            control_change(15, 1, 5)
+        ]
+    );
+}
+
+/// Values of a channel cleared by reset all controllers.
+#[test]
+fn reset_all_controllers() {
+    let mut state = ControllerState::new();
+
+    state.process(&control_change(0, 7, 100));
+    state.process(&control_change(1, 7, 100));
+    state.process(&control_change(0, 121, 0));
+
+    assert_eq!(state.value(0, 7), None);
+    assert_eq!(state.value(1, 7), Some(100));
+
+    state.clear();
+    assert_eq!(state.messages().count(), 0);
+}
@@ -0,0 +1,131 @@
+//! Human-readable rendering of messages.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{HexBytes, MidiMessage, SystemCommonMessage, SystemRealtimeMessage};
+
+/// Note names within an octave, starting with C.
+const NOTE_NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// Returns a human-readable description of a message like `NoteOn ch=1 note=C4 vel=100`.
+///
+/// Channels are rendered 1-based, note numbers as names with note 60 being `C4`
+/// and raw message bytes as hex.
+pub fn describe(msg: &MidiMessage) -> String {
+    let mut text = String::new();
+    // Only used for channel voice messages.
+    let channel = msg.channel_1based().unwrap_or_default();
+
+    // Writing to a string can't fail.
+    let _ = match *msg {
+        MidiMessage::NoteOff { note, velocity, .. } => write!(
+            text,
+            "NoteOff ch={} note={} vel={}",
+            channel,
+            NoteName(note),
+            velocity
+        ),
+        MidiMessage::NoteOn { note, velocity, .. } => write!(
+            text,
+            "NoteOn ch={} note={} vel={}",
+            channel,
+            NoteName(note),
+            velocity
+        ),
+        MidiMessage::PolyPressure { note, pressure, .. } => write!(
+            text,
+            "PolyPressure ch={} note={} pressure={}",
+            channel,
+            NoteName(note),
+            pressure
+        ),
+        MidiMessage::ControlChange {
+            controller, value, ..
+        } => match controller_name(controller) {
+            Some(name) => write!(
+                text,
+                "ControlChange ch={} cc={} ({}) value={}",
+                channel, controller, name, value
+            ),
+            None => write!(
+                text,
+                "ControlChange ch={} cc={} value={}",
+                channel, controller, value
+            ),
+        },
+        MidiMessage::ProgramChange { program, .. } => {
+            write!(text, "ProgramChange ch={} program={}", channel, program)
+        }
+        MidiMessage::ChannelPressure { pressure, .. } => {
+            write!(text, "ChannelPressure ch={} pressure={}", channel, pressure)
+        }
+        MidiMessage::PitchBend { value, .. } => {
+            write!(text, "PitchBend ch={} value={}", channel, value)
+        }
+        MidiMessage::SystemCommon(message) => match message {
+            SystemCommonMessage::QuarterFrame {
+                message_type,
+                value,
+            } => write!(text, "QuarterFrame type={} value={}", message_type, value),
+            SystemCommonMessage::SongPosition(position) => {
+                write!(text, "SongPosition {}", position)
+            }
+            SystemCommonMessage::SongSelect(song) => write!(text, "SongSelect {}", song),
+            SystemCommonMessage::TuneRequest => write!(text, "TuneRequest"),
+        },
+        MidiMessage::SystemRealtime(message) => match message {
+            SystemRealtimeMessage::Undefined(status) => write!(text, "Undefined {:02X}", status),
+            _ => write!(text, "{:?}", message),
+        },
+        MidiMessage::SysExStart => write!(text, "SysExStart"),
+        MidiMessage::SysEx(bytes) => write!(text, "SysEx {}", HexBytes(bytes)),
+        MidiMessage::SysExChunk(bytes) => write!(text, "SysExChunk {}", HexBytes(bytes)),
+        MidiMessage::SysExEnd => write!(text, "SysExEnd"),
+    };
+
+    text
+}
+
+/// Returns the name of commonly used controllers.
+fn controller_name(controller: u8) -> Option<&'static str> {
+    let name = match controller {
+        0 => "Bank Select",
+        1 => "Modulation",
+        2 => "Breath",
+        4 => "Foot",
+        5 => "Portamento Time",
+        6 => "Data Entry",
+        7 => "Volume",
+        8 => "Balance",
+        10 => "Pan",
+        11 => "Expression",
+        32 => "Bank Select LSB",
+        64 => "Sustain",
+        65 => "Portamento",
+        66 => "Sostenuto",
+        67 => "Soft Pedal",
+        74 => "Brightness",
+        120 => "All Sound Off",
+        121 => "Reset All Controllers",
+        123 => "All Notes Off",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+/// Note number displayed as name with octave.
+struct NoteName(u8);
+
+impl core::fmt::Display for NoteName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let octave = (self.0 / 12) as i8 - 1;
+        write!(f, "{}{}", NOTE_NAMES[self.0 as usize % 12], octave)
+    }
+}
+
+#[cfg(test)]
+mod tests;
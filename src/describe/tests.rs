@@ -0,0 +1,89 @@
+//! Unit tests
+
+use super::*;
+
+/// Rendering of channel voice messages.
+#[test]
+fn describe_channel_voice() {
+    let cases = [
+        (
+            MidiMessage::NoteOn {
+                channel: 0,
+                note: 60,
+                velocity: 100,
+            },
+            "NoteOn ch=1 note=C4 vel=100",
+        ),
+        (
+            MidiMessage::NoteOff {
+                channel: 15,
+                note: 1,
+                velocity: 0,
+            },
+            "NoteOff ch=16 note=C#-1 vel=0",
+        ),
+        (
+            MidiMessage::ControlChange {
+                channel: 2,
+                controller: 7,
+                value: 90,
+            },
+            "ControlChange ch=3 cc=7 (Volume) value=90",
+        ),
+        (
+            MidiMessage::ControlChange {
+                channel: 2,
+                controller: 20,
+                value: 1,
+            },
+            "ControlChange ch=3 cc=20 value=1",
+        ),
+        (
+            MidiMessage::PitchBend {
+                channel: 0,
+                value: 8192,
+            },
+            "PitchBend ch=1 value=8192",
+        ),
+    ];
+
+    for (message, text) in cases {
+        assert_eq!(describe(&message), text);
+    }
+}
+
+/// Rendering of system messages.
+#[test]
+fn describe_system() {
+    assert_eq!(
+        describe(&MidiMessage::SysEx(&[0xF0, 0x41, 0x10, 0xF7])),
+        "SysEx F0 41 10 F7"
+    );
+    assert_eq!(
+        describe(&MidiMessage::SystemCommon(SystemCommonMessage::SongSelect(
+            5
+        ))),
+        "SongSelect 5"
+    );
+    assert_eq!(
+        describe(&MidiMessage::SystemCommon(
+            SystemCommonMessage::QuarterFrame {
+                message_type: 1,
+                value: 5
+            }
+        )),
+        "QuarterFrame type=1 value=5"
+    );
+    assert_eq!(
+        describe(&MidiMessage::SystemRealtime(
+            SystemRealtimeMessage::TimingClock
+        )),
+        "TimingClock"
+    );
+    assert_eq!(
+        describe(&MidiMessage::SystemRealtime(
+            SystemRealtimeMessage::Undefined(0xFD)
+        )),
+        "Undefined FD"
+    );
+}
@@ -0,0 +1,230 @@
+//! Encoder converting typed messages back into bytes.
+
+use crate::{MidiMessage, SystemCommonMessage, SystemRealtimeMessage};
+
+/// Error variants for encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    /// Channel above 15, data byte above `0x7F` or 14-bit value above `0x3FFF`.
+    DataOutOfRange,
+
+    /// Raw message bytes don't start with a matching status byte.
+    InvalidStatus,
+
+    /// Output buffer too small for the message.
+    BufferTooSmall,
+}
+
+/// Encoder converting typed messages into bytes.
+#[derive(Debug, Clone)]
+pub struct MidiStreamEncoder {
+    /// Encode note off messages with status `0x8n` instead of note on with velocity 0.
+    prefer_note_off: bool,
+}
+
+impl Default for MidiStreamEncoder {
+    /// Returns a new encoder with default values.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MidiStreamEncoder {
+    /// Returns a new encoder.
+    pub fn new() -> Self {
+        Self {
+            prefer_note_off: true,
+        }
+    }
+
+    /// Set whether [`MidiMessage::NoteOff`] is encoded with status `0x8n` (default)
+    /// or as note on `0x9n` with velocity 0. The latter drops the release velocity
+    /// but allows running status to be kept by the receiving side.
+    pub fn set_prefer_note_off(&mut self, prefer_note_off: bool) {
+        self.prefer_note_off = prefer_note_off;
+    }
+
+    /// Encode a message into `out` and return the number of bytes written.
+    /// All fields are validated, so no corrupt stream is produced.
+    pub fn encode(&self, message: &MidiMessage, out: &mut [u8]) -> Result<usize, EncodeError> {
+        let mut buf = [0; 3];
+
+        let bytes: &[u8] = match *message {
+            MidiMessage::NoteOff {
+                channel,
+                note,
+                velocity,
+            } => {
+                if self.prefer_note_off {
+                    channel_message(&mut buf, 0x80, channel, &[note, velocity])?
+                } else {
+                    check_data(&[velocity])?;
+                    channel_message(&mut buf, 0x90, channel, &[note, 0])?
+                }
+            }
+            MidiMessage::NoteOn {
+                channel,
+                note,
+                velocity,
+            } => channel_message(&mut buf, 0x90, channel, &[note, velocity])?,
+            MidiMessage::PolyPressure {
+                channel,
+                note,
+                pressure,
+            } => channel_message(&mut buf, 0xA0, channel, &[note, pressure])?,
+            MidiMessage::ControlChange {
+                channel,
+                controller,
+                value,
+            } => channel_message(&mut buf, 0xB0, channel, &[controller, value])?,
+            MidiMessage::ProgramChange { channel, program } => {
+                channel_message(&mut buf, 0xC0, channel, &[program])?
+            }
+            MidiMessage::ChannelPressure { channel, pressure } => {
+                channel_message(&mut buf, 0xD0, channel, &[pressure])?
+            }
+            MidiMessage::PitchBend { channel, value } => {
+                if value > 0x3FFF {
+                    return Err(EncodeError::DataOutOfRange);
+                }
+                let data = [(value & 0x7F) as u8, (value >> 7) as u8];
+                channel_message(&mut buf, 0xE0, channel, &data)?
+            }
+            MidiMessage::SystemCommon(message) => match message {
+                SystemCommonMessage::QuarterFrame {
+                    message_type,
+                    value,
+                } => {
+                    if message_type > 0x07 || value > 0x0F {
+                        return Err(EncodeError::DataOutOfRange);
+                    }
+                    buf[0] = 0xF1;
+                    buf[1] = message_type << 4 | value;
+                    &buf[0..2]
+                }
+                SystemCommonMessage::SongPosition(position) => {
+                    if position > 0x3FFF {
+                        return Err(EncodeError::DataOutOfRange);
+                    }
+                    buf = [0xF2, (position & 0x7F) as u8, (position >> 7) as u8];
+                    &buf
+                }
+                SystemCommonMessage::SongSelect(song) => {
+                    check_data(&[song])?;
+                    buf[0] = 0xF3;
+                    buf[1] = song;
+                    &buf[0..2]
+                }
+                SystemCommonMessage::TuneRequest => {
+                    buf[0] = 0xF6;
+                    &buf[0..1]
+                }
+            },
+            MidiMessage::SystemRealtime(message) => {
+                buf[0] = match message {
+                    SystemRealtimeMessage::TimingClock => 0xF8,
+                    SystemRealtimeMessage::Start => 0xFA,
+                    SystemRealtimeMessage::Continue => 0xFB,
+                    SystemRealtimeMessage::Stop => 0xFC,
+                    SystemRealtimeMessage::ActiveSensing => 0xFE,
+                    SystemRealtimeMessage::SystemReset => 0xFF,
+                    SystemRealtimeMessage::Undefined(status @ (0xF9 | 0xFD)) => status,
+                    SystemRealtimeMessage::Undefined(_) => return Err(EncodeError::InvalidStatus),
+                };
+                &buf[0..1]
+            }
+            MidiMessage::SysExStart => {
+                buf[0] = 0xF0;
+                &buf[0..1]
+            }
+            MidiMessage::SysExChunk(bytes) => {
+                if bytes
+                    .iter()
+                    .any(|byte| *byte > 0x7F && *byte != 0xF0 && *byte != 0xF7)
+                {
+                    return Err(EncodeError::DataOutOfRange);
+                }
+                bytes
+            }
+            MidiMessage::SysExEnd => {
+                buf[0] = 0xF7;
+                &buf[0..1]
+            }
+            MidiMessage::SysEx(bytes) => {
+                if bytes.len() < 2 || bytes[0] != 0xF0 || bytes[bytes.len() - 1] != 0xF7 {
+                    return Err(EncodeError::InvalidStatus);
+                }
+                check_data(&bytes[1..bytes.len() - 1])?;
+                bytes
+            }
+        };
+
+        let out = out
+            .get_mut(..bytes.len())
+            .ok_or(EncodeError::BufferTooSmall)?;
+        out.copy_from_slice(bytes);
+
+        Ok(bytes.len())
+    }
+
+    /// Encode note on messages for all `(note, velocity)` pairs into `out` and return
+    /// the number of bytes written. The status byte is written only once, all further
+    /// notes use running status. Nothing is written for an empty chord.
+    pub fn encode_chord(
+        &self,
+        channel: u8,
+        notes: &[(u8, u8)],
+        out: &mut [u8],
+    ) -> Result<usize, EncodeError> {
+        if notes.is_empty() {
+            return Ok(0);
+        }
+        if channel > 0x0F {
+            return Err(EncodeError::DataOutOfRange);
+        }
+        for (note, velocity) in notes {
+            check_data(&[*note, *velocity])?;
+        }
+
+        let length = 1 + 2 * notes.len();
+        let out = out.get_mut(..length).ok_or(EncodeError::BufferTooSmall)?;
+
+        out[0] = 0x90 | channel;
+        for ((note, velocity), data) in notes.iter().zip(out[1..].chunks_exact_mut(2)) {
+            data[0] = *note;
+            data[1] = *velocity;
+        }
+
+        Ok(length)
+    }
+}
+
+/// Write a channel voice message into `buf` after validating channel and data bytes.
+fn channel_message<'a>(
+    buf: &'a mut [u8; 3],
+    status: u8,
+    channel: u8,
+    data: &[u8],
+) -> Result<&'a [u8], EncodeError> {
+    if channel > 0x0F {
+        return Err(EncodeError::DataOutOfRange);
+    }
+    check_data(data)?;
+
+    buf[0] = status | channel;
+    buf[1..=data.len()].copy_from_slice(data);
+
+    Ok(&buf[..=data.len()])
+}
+
+/// Returns an error if any of the bytes exceeds 7 bits.
+fn check_data(data: &[u8]) -> Result<(), EncodeError> {
+    if data.iter().any(|byte| *byte > 0x7F) {
+        return Err(EncodeError::DataOutOfRange);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
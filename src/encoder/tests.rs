@@ -0,0 +1,226 @@
+//! Unit tests
+
+use super::*;
+
+/// Encode a message into a fresh buffer.
+fn encode(message: MidiMessage) -> Result<Vec<u8>, EncodeError> {
+    let mut out = [0; 16];
+    let length = MidiStreamEncoder::new().encode(&message, &mut out)?;
+    Ok(out[..length].to_vec())
+}
+
+/// Encoding of valid messages.
+#[test]
+fn encode_messages() {
+    assert_eq!(
+        encode(MidiMessage::NoteOn {
+            channel: 1,
+            note: 60,
+            velocity: 127
+        }),
+        Ok(vec![0x91, 60, 127])
+    );
+    assert_eq!(
+        encode(MidiMessage::ProgramChange {
+            channel: 15,
+            program: 5
+        }),
+        Ok(vec![0xCF, 5])
+    );
+    assert_eq!(
+        encode(MidiMessage::PitchBend {
+            channel: 0,
+            value: 0x3FFF
+        }),
+        Ok(vec![0xE0, 0x7F, 0x7F])
+    );
+    assert_eq!(
+        encode(MidiMessage::SystemRealtime(SystemRealtimeMessage::Stop)),
+        Ok(vec![0xFC])
+    );
+    assert_eq!(
+        encode(MidiMessage::SystemCommon(
+            SystemCommonMessage::QuarterFrame {
+                message_type: 7,
+                value: 2
+            }
+        )),
+        Ok(vec![0xF1, 0x72])
+    );
+    assert_eq!(
+        encode(MidiMessage::SystemCommon(
+            SystemCommonMessage::SongPosition(0x3FFF)
+        )),
+        Ok(vec![0xF2, 0x7F, 0x7F])
+    );
+    assert_eq!(
+        encode(MidiMessage::SystemCommon(SystemCommonMessage::TuneRequest)),
+        Ok(vec![0xF6])
+    );
+    assert_eq!(
+        encode(MidiMessage::SysEx(&[0xF0, 0x41, 0x7F, 0xF7])),
+        Ok(vec![0xF0, 0x41, 0x7F, 0xF7])
+    );
+}
+
+/// Data fields just above their range.
+#[test]
+fn data_out_of_range() {
+    let messages = [
+        MidiMessage::NoteOn {
+            channel: 16,
+            note: 60,
+            velocity: 100,
+        },
+        MidiMessage::NoteOn {
+            channel: 0,
+            note: 128,
+            velocity: 100,
+        },
+        MidiMessage::NoteOff {
+            channel: 0,
+            note: 60,
+            velocity: 200,
+        },
+        MidiMessage::PolyPressure {
+            channel: 0,
+            note: 60,
+            pressure: 128,
+        },
+        MidiMessage::ControlChange {
+            channel: 0,
+            controller: 128,
+            value: 0,
+        },
+        MidiMessage::ControlChange {
+            channel: 0,
+            controller: 7,
+            value: 128,
+        },
+        MidiMessage::ProgramChange {
+            channel: 0,
+            program: 128,
+        },
+        MidiMessage::ChannelPressure {
+            channel: 0,
+            pressure: 128,
+        },
+        MidiMessage::PitchBend {
+            channel: 0,
+            value: 0x4000,
+        },
+        MidiMessage::SystemCommon(SystemCommonMessage::SongSelect(128)),
+        MidiMessage::SystemCommon(SystemCommonMessage::SongPosition(0x4000)),
+        MidiMessage::SystemCommon(SystemCommonMessage::QuarterFrame {
+            message_type: 8,
+            value: 0,
+        }),
+        MidiMessage::SystemCommon(SystemCommonMessage::QuarterFrame {
+            message_type: 0,
+            value: 16,
+        }),
+        MidiMessage::SysEx(&[0xF0, 0x41, 0x80, 0xF7]),
+    ];
+
+    for message in messages {
+        assert_eq!(encode(message), Err(EncodeError::DataOutOfRange));
+    }
+}
+
+/// Raw messages with wrong status bytes and too small buffers.
+#[test]
+fn invalid_messages() {
+    assert_eq!(
+        encode(MidiMessage::SysEx(&[0xF0, 0x41])),
+        Err(EncodeError::InvalidStatus)
+    );
+    assert_eq!(
+        encode(MidiMessage::SystemRealtime(
+            SystemRealtimeMessage::Undefined(0xF8)
+        )),
+        Err(EncodeError::InvalidStatus)
+    );
+
+    let mut out = [0; 2];
+    let message = MidiMessage::NoteOn {
+        channel: 0,
+        note: 60,
+        velocity: 100,
+    };
+    assert_eq!(
+        MidiStreamEncoder::new().encode(&message, &mut out),
+        Err(EncodeError::BufferTooSmall)
+    );
+}
+
+/// Note off encoded with its own status and as note on with velocity 0.
+#[test]
+fn prefer_note_off() {
+    let message = MidiMessage::NoteOff {
+        channel: 2,
+        note: 60,
+        velocity: 64,
+    };
+    let mut encoder = MidiStreamEncoder::new();
+    let mut out = [0; 3];
+
+    assert_eq!(encoder.encode(&message, &mut out), Ok(3));
+    assert_eq!(out, [0x82, 60, 64]);
+
+    encoder.set_prefer_note_off(false);
+
+    assert_eq!(encoder.encode(&message, &mut out), Ok(3));
+    assert_eq!(out, [0x92, 60, 0]);
+
+    let message = MidiMessage::NoteOff {
+        channel: 2,
+        note: 60,
+        velocity: 200,
+    };
+    assert_eq!(
+        encoder.encode(&message, &mut out),
+        Err(EncodeError::DataOutOfRange)
+    );
+}
+
+/// Chord encoded with running status and parsed back.
+#[test]
+fn encode_chord() {
+    let encoder = MidiStreamEncoder::new();
+    let mut out = [0; 8];
+
+    let length = encoder
+        .encode_chord(3, &[(60, 100), (64, 90), (67, 80)], &mut out)
+        .unwrap();
+    assert_eq!(out[..length], [0x93, 60, 100, 64, 90, 67, 80]);
+
+    let mut parser = crate::MidiStreamParser::<0>::new();
+    let mut messages = Vec::new();
+    for byte in &out[..length] {
+        if let Some(message) = parser.parse_typed(*byte).unwrap() {
+            messages.push(message.to_static().unwrap());
+        }
+    }
+    assert_eq!(
+        messages,
+        [(60, 100), (64, 90), (67, 80)].map(|(note, velocity)| MidiMessage::NoteOn {
+            channel: 3,
+            note,
+            velocity
+        })
+    );
+
+    assert_eq!(encoder.encode_chord(0, &[], &mut out), Ok(0));
+    assert_eq!(
+        encoder.encode_chord(16, &[(60, 100)], &mut out),
+        Err(EncodeError::DataOutOfRange)
+    );
+    assert_eq!(
+        encoder.encode_chord(0, &[(60, 128)], &mut out),
+        Err(EncodeError::DataOutOfRange)
+    );
+    assert_eq!(
+        encoder.encode_chord(0, &[(60, 100); 4], &mut out),
+        Err(EncodeError::BufferTooSmall)
+    );
+}
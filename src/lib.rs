@@ -1,9 +1,91 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(not(test), no_std)]
 
+use core::convert::TryFrom;
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
+mod clock;
+#[cfg(feature = "alloc")]
+mod collect;
+mod controller;
+#[cfg(feature = "alloc")]
+mod describe;
+mod encoder;
+mod limiter;
+mod message;
+mod mpe;
+mod mtc;
+mod note;
+mod panic;
+mod quantizer;
+mod queue;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "test-util")]
+mod roundtrip;
+mod sds;
+mod source;
+mod span;
+mod status;
+mod sysex;
+#[cfg(feature = "trace")]
+mod trace;
+mod typed;
+mod ump;
+mod usb;
+
+pub use clock::{ClockDivider, ClockTracker};
+#[cfg(feature = "alloc")]
+pub use collect::{CollectMidi, MidiMessages};
+pub use controller::ControllerState;
+#[cfg(feature = "alloc")]
+pub use describe::describe;
+pub use encoder::{EncodeError, MidiStreamEncoder};
+pub use limiter::RateLimiter;
+pub use message::{
+    ChannelModeMessage, MessageFilter, MidiEvent, MidiMessage, SystemCommonMessage,
+    SystemRealtimeMessage,
+};
+pub use mpe::{MpeNote, MpeTracker, MpeZone};
+pub use mtc::{FrameRate, MtcAccumulator, MtcError, MtcTimecode, QuarterFrame};
+pub use note::note_to_frequency_q16;
+pub use panic::{Panic, PanicDetector};
+pub use quantizer::Quantizer;
+pub use queue::{MidiQueue, QueuedMessage};
+#[cfg(feature = "std")]
+pub use reader::{parse_from_reader_buffered, StreamEnd};
+#[cfg(feature = "test-util")]
+pub use roundtrip::roundtrip_eq;
+pub use sds::{SdsHeader, SdsRequest};
+pub use source::{FallibleParser, SourceOrParseError};
+pub use span::{message_spans, ExpandedMessage, ExpandedMessages, MessageSpans};
+pub use status::{
+    classify, decode_status, expected_message_length, ByteClass, StatusInfo, DATA_MAX,
+    REALTIME_MAX, REALTIME_MIN, STATUS_CHANNEL_MAX, STATUS_CHANNEL_MIN, SYSEX_END, SYSEX_START,
+    SYSTEM_COMMON_MAX, SYSTEM_COMMON_MIN,
+};
+pub use sysex::{
+    append_roland_checksum, manufacturer_id, sysex_hash, sysex_payload_range, HexBytes,
+    ManufacturerId, SinkError, SysEx, SysExHandler, SysExRouter, SysExSink,
+};
+#[cfg(feature = "trace")]
+pub use trace::{TraceDecision, TraceEntry, TRACE_LEN};
+pub use typed::TypedParser;
+pub use ump::{from_ump, to_ump, ump_group};
+pub use usb::UsbMidiParser;
+
 /// Parser type with internal states.
 /// Owns a buffer of `SYSEX_MAX_LEN` bytes for constructing SysEx messages.
-#[derive(Debug)]
+///
+/// The parser only holds plain data without interior mutability or raw pointers,
+/// so it is guaranteed to be `Send` and `Sync` and can be shared behind a `Mutex`.
+#[derive(Debug, Clone)]
 pub struct MidiStreamParser<const SYSEX_MAX_LEN: usize> {
     /// Buffer for message to be created.
     message: [u8; 3],
@@ -11,6 +93,9 @@ pub struct MidiStreamParser<const SYSEX_MAX_LEN: usize> {
     /// Length of message in buffer.
     message_length: usize,
 
+    /// Length of the complete message, looked up once when the status byte is received.
+    message_expected_length: usize,
+
     /// Single byte realtime message buffer.
     realtime_message: [u8; 1],
 
@@ -22,16 +107,446 @@ pub struct MidiStreamParser<const SYSEX_MAX_LEN: usize> {
 
     /// SysEx message length.
     sysex_message_length: usize,
+
+    /// Read position in the SysEx buffer when used as ring buffer.
+    sysex_ring_start: usize,
+
+    /// Status byte of the message in progress was received, not reused.
+    status_received: bool,
+
+    /// Last completed channel message used running status.
+    running_status_used: bool,
+
+    /// A status byte other than realtime was received.
+    warmed_up: bool,
+
+    /// Remaining bytes of a truncated SysEx message are dropped.
+    sysex_discarding: bool,
+
+    /// Last completed SysEx message was truncated.
+    sysex_truncated: bool,
+
+    /// Running status to restore after SysEx, 0 if none.
+    sysex_saved_status: u8,
+
+    /// Configuration options.
+    config: ParserConfig,
+
+    /// Filter applied to typed messages.
+    message_filter: Option<MessageFilter>,
+
+    /// Length of a truncated SysEx message retained for continuation, 0 if none.
+    sysex_fragment_length: usize,
+
+    /// Hook deciding if a SysEx message continues a retained fragment.
+    sysex_continuation: Option<Callback<SysExContinuation>>,
+
+    /// Largest SysEx message length reached so far.
+    sysex_high_water_mark: usize,
+
+    /// Hook called when a SysEx message reaches the warning threshold.
+    sysex_warning: Option<Callback<fn(usize)>>,
+
+    /// Set once the warning threshold was reached by the current SysEx message.
+    sysex_warned: bool,
+
+    /// Hook called when running status is started or broken.
+    running_status_hook: Option<Callback<fn(RunningStatus)>>,
+
+    /// Copy or location of the message returned last.
+    last_message: LastMessage,
+
+    /// Timestamp of the last byte other than realtime fed to [`MidiStreamParser::parse_timed`].
+    last_timestamp: u32,
+
+    /// Set while the bytes following a status byte dropped by
+    /// [`MidiStreamParser::parse_with_filter`] are dropped.
+    filter_dropping: bool,
+
+    /// Number of bytes left over by the last call of [`MidiStreamParser::parse_dma`],
+    /// which are expected at the start of the next buffer.
+    dma_retained: usize,
+
+    /// Last parse decisions.
+    #[cfg(feature = "trace")]
+    trace: trace::Trace,
+}
+
+/// Configuration options for the parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserConfig {
+    /// Report malformed input as errors instead of silently recovering from it.
+    pub strict: bool,
+
+    /// Follow the MIDI specification where the default behavior is kept for backward
+    /// compatibility: tune request is returned as message, system common messages cancel
+    /// running status and any status byte other than realtime terminates a running SysEx
+    /// message. By default, tune request is not returned, MTC quarter frame, song position
+    /// pointer and song select keep running status and status bytes inside SysEx are
+    /// ignored.
+    pub spec_compliant: bool,
+
+    /// Return the single byte `0xF0` when a SysEx message starts,
+    /// decoded as [`MidiMessage::SysExStart`] by the typed API.
+    pub emit_sysex_start: bool,
+
+    /// Use the SysEx buffer as ring buffer that is drained with
+    /// [`MidiStreamParser::take_sysex_chunk`] while the message is still arriving.
+    /// The end of the message is signalled by returning the single byte `0xF7`,
+    /// decoded as [`MidiMessage::SysExEnd`] by the typed API.
+    /// A message interrupted by a status byte discards the bytes not drained yet.
+    pub sysex_ring_buffer: bool,
+
+    /// Silently drop data bytes until the first status byte other than realtime has
+    /// been received, instead of returning [`ParserError::InvalidStatus`] for each one.
+    pub drop_until_first_status: bool,
+
+    /// Close a SysEx message at the end of the buffer instead of returning
+    /// [`ParserError::SysexOverflow`]. The last byte of the buffer is replaced with `0xF7`,
+    /// the remaining bytes up to the real end of SysEx are dropped.
+    /// Such messages are flagged by [`MidiStreamParser::last_sysex_truncated`].
+    /// Has no effect in ring buffer mode or with `SYSEX_MAX_LEN` below 2.
+    pub truncate_sysex_on_overflow: bool,
+
+    /// Number of bytes in the SysEx buffer at which the hook set with
+    /// [`MidiStreamParser::set_sysex_warning`] is called, e.g. 90% of `SYSEX_MAX_LEN`.
+    /// 0 disables the warning.
+    pub sysex_warn_threshold: usize,
+
+    /// Drop universal SysEx messages (`0xF0 0x7E` and `0xF0 0x7F`) not addressed to this
+    /// device ID or to all devices (`0x7F`). Manufacturer specific messages are not
+    /// filtered, as the position of their device ID is not standardized.
+    /// Has no effect in ring buffer mode.
+    pub sysex_device_id: Option<u8>,
+
+    /// Keep the running status of channel messages across SysEx messages, as expected
+    /// from some older devices. By default, SysEx cancels running status as specified.
+    pub preserve_running_status_across_sysex: bool,
+
+    /// Echo the byte after parsing it in [`MidiStreamParser::parse_and_echo`]
+    /// instead of before.
+    pub echo_after_parse: bool,
+
+    /// Maximum time between two bytes of a channel or system common message in
+    /// [`MidiStreamParser::parse_timed`], in the units of the timestamps. An incomplete
+    /// message is discarded together with its status byte when the next byte arrives later.
+    /// Realtime bytes in between are not taken into account. `None` disables the timeout.
+    pub stale_message_timeout: Option<u32>,
+
+    /// Reset the parsing state like [`MidiStreamParser::reset`] whenever an error is
+    /// returned, so parsing resynchronizes on the next status byte instead of continuing
+    /// with a possibly corrupted message in progress.
+    /// Errors caused by realtime bytes don't reset the state.
+    pub reset_on_error: bool,
+}
+
+impl ParserConfig {
+    /// Returns the default configuration, usable in const context.
+    pub const fn new() -> Self {
+        Self {
+            strict: false,
+            spec_compliant: false,
+            emit_sysex_start: false,
+            sysex_ring_buffer: false,
+            drop_until_first_status: false,
+            truncate_sysex_on_overflow: false,
+            sysex_warn_threshold: 0,
+            sysex_device_id: None,
+            preserve_running_status_across_sysex: false,
+            echo_after_parse: false,
+            stale_message_timeout: None,
+            reset_on_error: false,
+        }
+    }
+
+    /// Returns a configuration for spec-faithful parsing that reports all deviations.
+    ///
+    /// This preset enables [`spec_compliant`](Self::spec_compliant), so that tune request
+    /// is returned immediately and, like all system common messages, cancels running status,
+    /// and SysEx is terminated by any status byte other than realtime.
+    ///
+    /// It also enables [`strict`](Self::strict), so that incomplete messages interrupted
+    /// by any status byte other than realtime, including an end of SysEx without start, SysEx
+    /// messages without data, SysEx messages interrupted by a status byte and undefined
+    /// realtime status bytes are reported as errors. All other options keep their
+    /// defaults, including running status being cancelled by SysEx.
+    ///
+    /// The undefined system common status bytes `0xF4` and `0xF5` cancel running status and
+    /// an end of SysEx without start doesn't return a message in every configuration.
+    pub const fn strict() -> Self {
+        Self {
+            strict: true,
+            spec_compliant: true,
+            ..Self::new()
+        }
+    }
+}
+
+impl Default for ParserConfig {
+    /// Returns the default configuration, see [`ParserConfig::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Snapshot of the internal parser state, see [`MidiStreamParser::snapshot`].
+///
+/// Contains a full copy of the SysEx buffer, so it occupies about as much memory
+/// as the parser itself and taking a snapshot copies `SYSEX_MAX_LEN` bytes.
+#[derive(Debug, Clone)]
+pub struct ParserState<const SYSEX_MAX_LEN: usize> {
+    /// Buffer for message to be created.
+    message: [u8; 3],
+
+    /// Length of message in buffer.
+    message_length: usize,
+
+    /// Single byte realtime message buffer.
+    realtime_message: [u8; 1],
+
+    /// State of SysEx parsing.
+    sysex_running: bool,
+
+    /// SysEx message buffer.
+    sysex_message: [u8; SYSEX_MAX_LEN],
+
+    /// SysEx message length.
+    sysex_message_length: usize,
+
+    /// Read position in the SysEx buffer when used as ring buffer.
+    sysex_ring_start: usize,
+
+    /// Status byte of the message in progress was received, not reused.
+    status_received: bool,
+
+    /// Last completed channel message used running status.
+    running_status_used: bool,
+
+    /// A status byte other than realtime was received.
+    warmed_up: bool,
+
+    /// Remaining bytes of a truncated SysEx message are dropped.
+    sysex_discarding: bool,
+
+    /// Last completed SysEx message was truncated.
+    sysex_truncated: bool,
+
+    /// Running status to restore after SysEx, 0 if none.
+    sysex_saved_status: u8,
+
+    /// Length of a truncated SysEx message retained for continuation.
+    sysex_fragment_length: usize,
+}
+
+/// Length of the header written by [`MidiStreamParser::serialize_sysex_state`].
+const SYSEX_STATE_HEADER_LEN: usize = 5;
+
+/// Number of SysEx bytes kept by [`MidiStreamParser::parse_to_sink`], up to the device ID.
+const SYSEX_SINK_HEAD_LEN: usize = 3;
+
+/// Parsing state after the last complete message, restored by
+/// [`MidiStreamParser::parse_dma`] if a buffer ends in the middle of a message.
+#[derive(Debug, Clone, Copy)]
+struct DmaBoundary {
+    message: [u8; 3],
+    message_length: usize,
+    message_expected_length: usize,
+    status_received: bool,
+    warmed_up: bool,
+    sysex_running: bool,
+    sysex_message_length: usize,
+    sysex_ring_start: usize,
+    sysex_discarding: bool,
+    sysex_saved_status: u8,
+    sysex_fragment_length: usize,
+    sysex_warned: bool,
+
+    /// Start of a retained fragment, which is overwritten by a SysEx message
+    /// not continuing it.
+    sysex_fragment_start: [u8; 4],
+}
+
+/// Message returned last by the parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LastMessage {
+    /// No message returned yet or not available anymore.
+    None,
+
+    /// Copy of a message without SysEx payload and its length.
+    Short([u8; 3], usize),
+
+    /// SysEx message of the given length at the start of the SysEx buffer.
+    SysEx(usize),
+}
+
+/// Hook deciding if a SysEx message continues a retained fragment.
+type SysExContinuation = fn(&[u8]) -> bool;
+
+/// Callback stored in the parser, wrapped to provide a `Debug` implementation.
+#[derive(Clone, Copy)]
+struct Callback<F>(F);
+
+impl<F> fmt::Debug for Callback<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Callback")
+    }
 }
 
 /// Error variants.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParserError {
     /// No valid status byte.
     InvalidStatus,
 
     /// SysEx message longer than SYSEX_MAX_LEN bytes.
     SysexOverflow,
+
+    /// Message interrupted by a status byte before all data bytes were received.
+    /// Only reported in strict mode.
+    TruncatedMessage,
+
+    /// SysEx message without any data bytes.
+    /// Only reported in strict mode.
+    EmptySysEx,
+
+    /// Undefined system realtime status byte `0xF9` or `0xFD`.
+    /// Only reported in strict mode, otherwise passed as realtime message.
+    UndefinedStatus(u8),
+
+    /// Message length doesn't match its status byte.
+    InvalidLength,
+
+    /// Data byte with the most significant bit set.
+    InvalidData,
+
+    /// Universal MIDI Packet with an unsupported message type.
+    InvalidMessageType,
+
+    /// Message borrows the parser buffer and can't be stored in an owned slot.
+    BorrowedMessage,
+
+    /// SysEx sink failed to take the payload.
+    Sink(SinkError),
+
+    /// SysEx message interrupted by a status byte other than realtime or end of SysEx.
+    /// Only reported in strict mode, otherwise the SysEx message is discarded.
+    SysExDataCorrupt,
+}
+
+impl From<&ParserError> for u8 {
+    /// Returns a stable numeric code for the error:
+    ///
+    /// | Error                 | Code        |
+    /// |-----------------------|-------------|
+    /// | `InvalidStatus`       | 1           |
+    /// | `SysexOverflow`       | 2           |
+    /// | `TruncatedMessage`    | 3           |
+    /// | `EmptySysEx`          | 4           |
+    /// | `InvalidLength`       | 5           |
+    /// | `InvalidData`         | 6           |
+    /// | `InvalidMessageType`  | 7           |
+    /// | `BorrowedMessage`     | 8           |
+    /// | `Sink`                | 9           |
+    /// | `SysExDataCorrupt`    | 10          |
+    /// | `UndefinedStatus(b)`  | status `b`  |
+    ///
+    /// Codes of undefined status bytes are in the range `0x80..=0xFF`.
+    fn from(error: &ParserError) -> Self {
+        match *error {
+            ParserError::InvalidStatus => 1,
+            ParserError::SysexOverflow => 2,
+            ParserError::TruncatedMessage => 3,
+            ParserError::EmptySysEx => 4,
+            ParserError::InvalidLength => 5,
+            ParserError::InvalidData => 6,
+            ParserError::InvalidMessageType => 7,
+            ParserError::BorrowedMessage => 8,
+            ParserError::Sink(_) => 9,
+            ParserError::SysExDataCorrupt => 10,
+            ParserError::UndefinedStatus(status) => status,
+        }
+    }
+}
+
+impl TryFrom<u8> for ParserError {
+    type Error = u8;
+
+    /// Returns the error for a numeric code, or the code itself if it is unknown.
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        match code {
+            1 => Ok(ParserError::InvalidStatus),
+            2 => Ok(ParserError::SysexOverflow),
+            3 => Ok(ParserError::TruncatedMessage),
+            4 => Ok(ParserError::EmptySysEx),
+            5 => Ok(ParserError::InvalidLength),
+            6 => Ok(ParserError::InvalidData),
+            7 => Ok(ParserError::InvalidMessageType),
+            8 => Ok(ParserError::BorrowedMessage),
+            9 => Ok(ParserError::Sink(SinkError)),
+            10 => Ok(ParserError::SysExDataCorrupt),
+            0x80..=0xFF => Ok(ParserError::UndefinedStatus(code)),
+            _ => Err(code),
+        }
+    }
+}
+
+/// Running status transition reported by the hook set with
+/// [`MidiStreamParser::set_running_status_hook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunningStatus {
+    /// Channel status byte different from the previous one received, starting a run
+    /// of messages that may use running status.
+    Started(u8),
+
+    /// Running status ended by a different channel status byte, a system common
+    /// message, SysEx or an undefined status byte.
+    Broken,
+}
+
+impl<const SYSEX_MAX_LEN: usize> PartialEq for MidiStreamParser<SYSEX_MAX_LEN> {
+    /// Compares the parsing state and configuration. Only the used parts of the message
+    /// and SysEx buffers are compared. Hooks, the message filter and the trace are not
+    /// taken into account.
+    fn eq(&self, other: &Self) -> bool {
+        self.message[..self.message_length] == other.message[..other.message_length]
+            && (self.message_length == 0
+                || self.message_expected_length == other.message_expected_length)
+            && self.realtime_message == other.realtime_message
+            && self.sysex_running == other.sysex_running
+            && self.sysex_message_length == other.sysex_message_length
+            && self.sysex_ring_start == other.sysex_ring_start
+            && self.status_received == other.status_received
+            && self.running_status_used == other.running_status_used
+            && self.warmed_up == other.warmed_up
+            && self.sysex_discarding == other.sysex_discarding
+            && self.sysex_truncated == other.sysex_truncated
+            && self.sysex_saved_status == other.sysex_saved_status
+            && self.config == other.config
+            && self.sysex_fragment_length == other.sysex_fragment_length
+            && self.sysex_high_water_mark == other.sysex_high_water_mark
+            && self.sysex_warned == other.sysex_warned
+            && self.last_message == other.last_message
+            && self.last_timestamp == other.last_timestamp
+            && self.filter_dropping == other.filter_dropping
+            && self.dma_retained == other.dma_retained
+            && self.used_sysex_bytes().eq(other.used_sysex_bytes())
+    }
+}
+
+impl<const SYSEX_MAX_LEN: usize> Eq for MidiStreamParser<SYSEX_MAX_LEN> {}
+
+/// Action of the filter passed to [`MidiStreamParser::parse_with_filter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterAction {
+    /// Parse the byte unchanged.
+    Pass,
+
+    /// Drop the byte without parsing it. Dropping a status byte drops the rest of its
+    /// message as well.
+    Drop,
+
+    /// Parse the given byte instead.
+    Replace(u8),
 }
 
 impl<const SYSEX_MAX_LEN: usize> Default for MidiStreamParser<SYSEX_MAX_LEN> {
@@ -43,91 +558,1189 @@ impl<const SYSEX_MAX_LEN: usize> Default for MidiStreamParser<SYSEX_MAX_LEN> {
 
 impl<const SYSEX_MAX_LEN: usize> MidiStreamParser<SYSEX_MAX_LEN> {
     /// Returns a new parser.
-    pub fn new() -> Self {
+    ///
+    /// Usable in const context, so a parser can be placed in a `static` without heap or
+    /// lazy initialization, e.g. inside a `Mutex<RefCell<...>>` of a critical section
+    /// implementation or a static cell. All buffers are zeroed.
+    ///
+    /// ```
+    /// use core::cell::RefCell;
+    /// use midi_stream_parser::MidiStreamParser;
+    ///
+    /// // Stands in for the mutex of a critical section implementation.
+    /// struct Shared(RefCell<MidiStreamParser<256>>);
+    ///
+    /// // Safety: only accessed from a single thread in this example.
+    /// unsafe impl Sync for Shared {}
+    ///
+    /// static PARSER: Shared = Shared(RefCell::new(MidiStreamParser::new()));
+    ///
+    /// let mut parser = PARSER.0.borrow_mut();
+    /// assert_eq!(parser.parse(0xF8).unwrap(), Some([0xF8].as_ref()));
+    /// ```
+    pub const fn new() -> Self {
+        Self::with_config(ParserConfig::new())
+    }
+
+    /// Returns a new parser using the given configuration, usable in const context.
+    pub const fn with_config(config: ParserConfig) -> Self {
         Self {
             message: [0; 3],
             message_length: 0,
+            message_expected_length: 0,
             realtime_message: [0; 1],
             sysex_running: false,
             sysex_message: [0; SYSEX_MAX_LEN],
             sysex_message_length: 0,
+            sysex_ring_start: 0,
+            status_received: false,
+            running_status_used: false,
+            warmed_up: false,
+            sysex_discarding: false,
+            sysex_truncated: false,
+            sysex_saved_status: 0,
+            config,
+            message_filter: None,
+            sysex_fragment_length: 0,
+            sysex_continuation: None,
+            sysex_high_water_mark: 0,
+            sysex_warning: None,
+            sysex_warned: false,
+            running_status_hook: None,
+            last_message: LastMessage::None,
+            last_timestamp: 0,
+            filter_dropping: false,
+            dma_retained: 0,
+            #[cfg(feature = "trace")]
+            trace: trace::Trace::new(),
         }
     }
 
+    /// Returns a new parser continuing a stream that uses running status of the channel
+    /// voice message `status`, so the first bytes fed can be data bytes.
+    /// Returns [`ParserError::InvalidStatus`] if `status` is not a channel voice status byte.
+    pub fn with_running_status(status: u8) -> Result<Self, ParserError> {
+        if !(STATUS_CHANNEL_MIN..=STATUS_CHANNEL_MAX).contains(&status) {
+            return Err(ParserError::InvalidStatus);
+        }
+
+        let mut parser = Self::new();
+        parser.set_status(status);
+        parser.warmed_up = true;
+
+        Ok(parser)
+    }
+
+    /// Returns the configuration of the parser.
+    pub fn config(&self) -> &ParserConfig {
+        &self.config
+    }
+
     /// Feed a byte into the parser and return result.
     /// The `Ok` variant is an option that contains either the constructed message or `None`
     /// in case the message is not ready yet.
+    #[must_use = "the completed message is lost if it is not used"]
     pub fn parse(&mut self, byte: u8) -> Result<Option<&[u8]>, ParserError> {
+        if byte == 0xF0 {
+            // SysEx buffer is about to be overwritten.
+            self.last_message = LastMessage::None;
+        }
+
+        let running_status = self.running_status();
+
+        let message = self.parse_byte(byte).map(|message| {
+            message.map(|message| {
+                if message.len() > 1 && message[0] == 0xF0 {
+                    LastMessage::SysEx(message.len())
+                } else {
+                    let mut bytes = [0; 3];
+                    bytes[..message.len()].copy_from_slice(message);
+                    LastMessage::Short(bytes, message.len())
+                }
+            })
+        });
+
+        #[cfg(feature = "trace")]
+        self.trace.record(TraceEntry {
+            byte,
+            decision: match message {
+                Ok(Some(LastMessage::Short(_, length) | LastMessage::SysEx(length))) => {
+                    TraceDecision::Emitted(length)
+                }
+                Ok(_) => TraceDecision::Pending,
+                Err(error) => TraceDecision::Error(error),
+            },
+        });
+
+        if message.is_err() && self.config.reset_on_error && byte < REALTIME_MIN {
+            // Realtime bytes never disturb a message in progress.
+            self.reset();
+        }
+
+        if let Some(Callback(hook)) = self.running_status_hook {
+            let new_running_status = self.running_status();
+            if new_running_status != running_status {
+                if running_status.is_some() {
+                    hook(RunningStatus::Broken);
+                }
+                if let Some(status) = new_running_status {
+                    hook(RunningStatus::Started(status));
+                }
+            }
+        }
+
+        self.last_message = match message? {
+            Some(message) => message,
+            None => return Ok(None),
+        };
+
+        Ok(self.last_message())
+    }
+
+    /// Returns an iterator over the last [`TRACE_LEN`] bytes fed into the parser and the
+    /// decisions taken for them, oldest first. Only available with the `trace` feature.
+    /// The trace is kept across [`reset`](Self::reset) for post-mortem debugging.
+    #[cfg(feature = "trace")]
+    pub fn trace_log(&self) -> impl Iterator<Item = TraceEntry> + '_ {
+        self.trace.iter()
+    }
+
+    /// Returns the message returned last by [`parse`](Self::parse), so it can be read
+    /// again before more bytes are fed. A SysEx message is not available anymore once
+    /// the next one starts.
+    pub fn last_message(&self) -> Option<&[u8]> {
+        match self.last_message {
+            LastMessage::None => None,
+            LastMessage::Short(ref bytes, length) => Some(&bytes[..length]),
+            LastMessage::SysEx(length) => Some(&self.sysex_message[..length]),
+        }
+    }
+
+    /// Feed a byte into the parser and return a completed message.
+    fn parse_byte(&mut self, byte: u8) -> Result<Option<&[u8]>, ParserError> {
+        // Any status byte except realtime discards an incomplete message.
+        let truncated = self.config.strict && (0x80..=0xF7).contains(&byte) && self.is_incomplete();
+
+        if (0x80..=0xF7).contains(&byte) {
+            // Real end of a truncated SysEx message or interrupted by another status byte.
+            self.sysex_discarding = false;
+        }
+
+        // Any status byte except realtime and start or end of SysEx interrupts SysEx.
+        let sysex_interrupted = self.sysex_running && matches!(byte, 0x80..=0xEF | 0xF1..=0xF6);
+        if sysex_interrupted && !self.config.spec_compliant {
+            // Ignored, the SysEx message continues.
+            if self.config.strict {
+                return Err(ParserError::SysExDataCorrupt);
+            }
+            return Ok(None);
+        }
+        if sysex_interrupted {
+            self.retain_sysex_fragment();
+            self.sysex_running = false;
+            self.sysex_saved_status = 0;
+            if self.config.sysex_ring_buffer {
+                // Aborted, the bytes not drained yet are discarded.
+                self.sysex_message_length = 0;
+                self.sysex_ring_start = 0;
+            }
+        }
+
+        if (0x80..=0xF6).contains(&byte) {
+            self.warmed_up = true;
+        } else if byte < 0x80 && !self.warmed_up && self.config.drop_until_first_status {
+            // Joined the stream in the middle of a message.
+            return Ok(None);
+        }
+
         match byte {
-            0x00..=0x7F => {
+            0..=DATA_MAX => {
                 // Data byte
                 if self.sysex_running {
-                    if self.sysex_message_length >= SYSEX_MAX_LEN {
-                        return Err(ParserError::SysexOverflow);
+                    if self.sysex_fragment_length > 0 && self.match_sysex_fragment(byte) {
+                        return Ok(None);
                     }
-                    self.sysex_message[self.sysex_message_length] = byte;
-                    self.sysex_message_length += 1;
+                    if self.sysex_truncation_pending() {
+                        self.sysex_running = false;
+                        self.sysex_discarding = true;
+                        self.restore_running_status(false);
+                        if !self.sysex_device_id_matches() {
+                            return Ok(None);
+                        }
+                        return Ok(Some(self.truncate_sysex()));
+                    }
+                    self.push_sysex(byte)?;
                 } else {
+                    if self.sysex_discarding {
+                        // Remainder of a truncated SysEx message.
+                        return Ok(None);
+                    }
                     if self.message_length == 0 {
                         // No valid status byte found.
                         return Err(ParserError::InvalidStatus);
                     }
                     self.message[self.message_length] = byte;
                     self.message_length += 1;
-                    if self.message_length == 3 {
-                        // 3-byte message ready, keep first byte for running status
-                        self.message_length = 1;
-                        return Ok(Some(&self.message));
-                    } else if matches!(self.message[0] & 0xF0, 0xC0 | 0xD0)
-                        || matches!(self.message[0], 0xF1 | 0xF3)
-                    {
-                        // 2-byte message ready, keep first byte for running status
-                        self.message_length = 1;
-                        return Ok(Some(&self.message[0..2]));
+                    let length = self.message_expected_length;
+                    if self.message_length == length {
+                        if self.message[0] < 0xF0 {
+                            // Channel message ready, keep first byte for running status
+                            self.complete_channel_message();
+                        } else if self.config.spec_compliant {
+                            // System common message ready, no running status
+                            self.message_length = 0;
+                        } else {
+                            // System common message ready, running status kept
+                            self.message_length = 1;
+                            self.status_received = false;
+                        }
+                        return Ok(Some(&self.message[..length]));
                     }
                 }
             }
-            0x80..=0xEF => {
+            STATUS_CHANNEL_MIN..=STATUS_CHANNEL_MAX => {
                 // Status byte for channel voice message.
-                self.message[0] = byte;
-                self.message_length = 1;
+                self.set_status(byte);
+                self.status_received = true;
             }
-            0xF0..=0xF7 => {
+            SYSEX_START..=SYSEX_END => {
                 // Status byte for system common message.
                 match byte {
-                    0xF0 => {
+                    SYSEX_START => {
                         // Start of SysEx.
+                        if self.sysex_running {
+                            self.retain_sysex_fragment();
+                        }
+                        if self.message_length > 0 && self.message[0] <= STATUS_CHANNEL_MAX {
+                            self.sysex_saved_status = self.message[0];
+                        }
                         self.message[0] = 0;
                         self.message_length = 0;
                         self.sysex_running = true;
-                        self.sysex_message[0] = byte;
-                        self.sysex_message_length = 1;
+                        self.sysex_warned = false;
+                        if self.config.sysex_ring_buffer {
+                            // Append to bytes not drained yet.
+                            self.push_sysex(byte)?;
+                        } else {
+                            self.sysex_message_length = 0;
+                            if SYSEX_MAX_LEN > 0 {
+                                self.sysex_message[0] = byte;
+                                self.sysex_message_length = 1;
+                                self.sysex_length_changed();
+                            }
+                        }
+                        if truncated {
+                            return Err(ParserError::TruncatedMessage);
+                        }
+                        if self.config.emit_sysex_start {
+                            return Ok(Some(&[0xF0]));
+                        }
                     }
-                    0xF7 => {
+                    SYSEX_END => {
                         // End of SysEx.
-                        self.sysex_running = false;
-                        if self.sysex_message_length >= SYSEX_MAX_LEN {
-                            return Err(ParserError::SysexOverflow);
+                        if !self.sysex_running {
+                            if truncated {
+                                // Cancels the incomplete message like a system common
+                                // status byte.
+                                self.message_length = 0;
+                                return Err(ParserError::TruncatedMessage);
+                            }
+                            // Not preceded by a start of SysEx, ignored without touching
+                            // a channel message in progress.
+                            return Ok(None);
+                        }
+                        let accepted = self.end_sysex(self.sysex_message_length == 1)?;
+                        if self.sysex_truncation_pending() {
+                            if !accepted {
+                                return Ok(None);
+                            }
+                            return Ok(Some(self.truncate_sysex()));
+                        }
+                        self.sysex_truncated = false;
+                        self.push_sysex(byte)?;
+                        if self.config.sysex_ring_buffer {
+                            return Ok(Some(&[0xF7]));
+                        }
+                        if !accepted {
+                            return Ok(None);
                         }
-                        self.sysex_message[self.sysex_message_length] = byte;
-                        self.sysex_message_length += 1;
                         return Ok(Some(&self.sysex_message[0..self.sysex_message_length]));
                     }
-                    _ => {
+                    0xF4 | 0xF5 => {
+                        // Undefined, cancels running status.
                         self.message[0] = byte;
-                        self.message_length = 1;
+                        self.message_length = 0;
+                    }
+                    0xF6 if !self.config.spec_compliant => {
+                        // Tune request, not returned but cancels running status.
+                        self.message[0] = byte;
+                        self.message_length = 0;
+                    }
+                    0xF6 => {
+                        // Tune request, single byte message without running status.
+                        self.message[0] = byte;
+                        self.message_length = 0;
+                        if truncated {
+                            return Err(ParserError::TruncatedMessage);
+                        }
+                        if sysex_interrupted && self.config.strict {
+                            return Err(ParserError::SysExDataCorrupt);
+                        }
+                        return Ok(Some(&self.message[0..1]));
+                    }
+                    _ => {
+                        self.set_status(byte);
+                        self.status_received = true;
                     }
                 }
             }
-            0xF8..=0xFF => {
+            REALTIME_MIN..=REALTIME_MAX => {
                 // Status byte for system realtime message.
+                if self.config.strict && matches!(byte, 0xF9 | 0xFD) {
+                    return Err(ParserError::UndefinedStatus(byte));
+                }
                 self.realtime_message[0] = byte;
                 return Ok(Some(&self.realtime_message));
             }
         }
 
+        if truncated {
+            return Err(ParserError::TruncatedMessage);
+        }
+
+        if sysex_interrupted && self.config.strict {
+            return Err(ParserError::SysExDataCorrupt);
+        }
+
         Ok(None)
     }
+
+    /// Feed all bytes of a buffer into the parser, passing each completed message or
+    /// error to `on_message` in the order they occur.
+    pub fn parse_slice(
+        &mut self,
+        bytes: &[u8],
+        mut on_message: impl FnMut(Result<&[u8], ParserError>),
+    ) {
+        for &byte in bytes {
+            match self.parse(byte) {
+                Ok(Some(message)) => on_message(Ok(message)),
+                Ok(None) => {}
+                Err(error) => on_message(Err(error)),
+            }
+        }
+    }
+
+    /// Parse a buffer filled by DMA, passing the completed messages to `on_message`, and
+    /// return the number of bytes consumed up to the end of the last complete message.
+    ///
+    /// If the buffer ends in the middle of a message, the parsing state taken by the message
+    /// is restored, so the remaining bytes can be kept and fed again at the start of the
+    /// next buffer. Realtime messages are passed as soon as they are seen, also inside the
+    /// incomplete message. The remaining bytes are expected at the start of the next buffer,
+    /// where their realtime messages are skipped, so they are not passed twice. Bytes
+    /// causing errors are skipped and count as consumed.
+    ///
+    /// The bytes of a SysEx message in progress are kept by the parser and count as
+    /// consumed, so a SysEx message may span any number of buffers. A channel or system
+    /// common message is only consumed once it is complete, so it needs to fit into the
+    /// buffer together with the bytes fed again. Hooks are called again for the bytes
+    /// fed again, e.g. the running status hook for a remaining status byte.
+    pub fn parse_dma(&mut self, buf: &[u8], mut on_message: impl FnMut(&[u8])) -> usize {
+        let retained = core::mem::replace(&mut self.dma_retained, 0);
+        let mut consumed = 0;
+        let mut boundary = self.dma_boundary();
+
+        for (index, &byte) in buf.iter().enumerate() {
+            if byte >= REALTIME_MIN && index < retained {
+                // Passed already by the previous call.
+                continue;
+            }
+
+            if let Ok(Some(message)) = self.parse(byte) {
+                on_message(message);
+            }
+
+            if !self.mid_message() || self.sysex_running {
+                consumed = index + 1;
+                boundary = self.dma_boundary();
+            }
+        }
+
+        if consumed < buf.len() {
+            self.restore_dma_boundary(&boundary);
+            self.dma_retained = buf.len() - consumed;
+        }
+
+        consumed
+    }
+
+    /// Reset the parsing state, discarding any message in progress.
+    /// The configuration, filters and hooks are kept.
+    pub fn reset(&mut self) {
+        if self.sysex_running {
+            self.retain_sysex_fragment();
+        }
+        self.message = [0; 3];
+        self.message_length = 0;
+        self.sysex_running = false;
+        self.sysex_message_length = 0;
+        self.sysex_ring_start = 0;
+        self.warmed_up = false;
+        self.sysex_discarding = false;
+        self.sysex_saved_status = 0;
+        self.last_message = LastMessage::None;
+        self.filter_dropping = false;
+        self.dma_retained = 0;
+    }
+
+    /// Abort a SysEx message in progress, discarding its bytes. Unlike [`reset`](Self::reset),
+    /// the running status of channel messages from before the SysEx message is kept.
+    pub fn clear_sysex(&mut self) {
+        if self.sysex_running {
+            self.sysex_running = false;
+            self.restore_running_status(true);
+        }
+        self.sysex_message_length = 0;
+        self.sysex_ring_start = 0;
+        self.sysex_fragment_length = 0;
+        self.sysex_discarding = false;
+    }
+
+    /// Move SysEx bytes received so far into `out` and return their number.
+    /// Only available with [`ParserConfig::sysex_ring_buffer`] enabled, otherwise 0 is returned.
+    pub fn take_sysex_chunk(&mut self, out: &mut [u8]) -> usize {
+        if !self.config.sysex_ring_buffer {
+            return 0;
+        }
+
+        let count = self.sysex_message_length.min(out.len());
+
+        for byte in out[..count].iter_mut() {
+            *byte = self.sysex_message[self.sysex_ring_start];
+            self.sysex_ring_start = (self.sysex_ring_start + 1) % SYSEX_MAX_LEN;
+        }
+        self.sysex_message_length -= count;
+
+        count
+    }
+
+    /// Set a hook called with the number of buffered bytes when a SysEx message reaches
+    /// [`ParserConfig::sysex_warn_threshold`], to detect messages approaching the buffer
+    /// size before they overflow. The hook is called at most once per message.
+    pub fn set_sysex_warning(&mut self, hook: Option<fn(usize)>) {
+        self.sysex_warning = hook.map(Callback);
+    }
+
+    /// Set a hook called on running status transitions while parsing, e.g. to measure how
+    /// efficiently a link uses running status. Resending the current status byte is not
+    /// a transition, a change to a different one reports [`RunningStatus::Broken`]
+    /// followed by [`RunningStatus::Started`]. Realtime messages don't affect running status.
+    pub fn set_running_status_hook(&mut self, hook: Option<fn(RunningStatus)>) {
+        self.running_status_hook = hook.map(Callback);
+    }
+
+    /// Set a hook for reassembling SysEx messages that were split by a reset or
+    /// by a new start of SysEx before their end.
+    ///
+    /// With a hook set, such a truncated SysEx message is retained. When the next
+    /// SysEx message starts with the same manufacturer ID, the hook is called with
+    /// the retained fragment. If it returns `true`, the payload of the new message is
+    /// appended to the fragment instead of starting a new message.
+    pub fn set_sysex_continuation(&mut self, hook: Option<fn(&[u8]) -> bool>) {
+        self.sysex_continuation = hook.map(Callback);
+        if hook.is_none() {
+            self.sysex_fragment_length = 0;
+        }
+    }
+
+    /// Set a filter for messages returned by [`parse_typed`](Self::parse_typed).
+    /// Messages rejected by the filter are returned as `None`.
+    pub fn set_message_filter(&mut self, filter: Option<MessageFilter>) {
+        self.message_filter = filter;
+    }
+
+    /// Feed a byte into the parser like [`parse`](Self::parse) after passing it to `filter`,
+    /// which decides to parse, drop or replace it, e.g. for remapping channels in a thru box.
+    ///
+    /// The parsing state follows the filtered stream: a replaced status byte becomes the
+    /// running status for the following data bytes. A dropped status byte drops the whole
+    /// message, so its data bytes, further data bytes using its running status and the end
+    /// of a dropped SysEx message are dropped without being passed to `filter`, up to the
+    /// next status byte other than realtime.
+    #[must_use = "the completed message is lost if it is not used"]
+    pub fn parse_with_filter(
+        &mut self,
+        byte: u8,
+        mut filter: impl FnMut(u8) -> FilterAction,
+    ) -> Result<Option<&[u8]>, ParserError> {
+        if self.filter_dropping && matches!(byte, 0x00..=DATA_MAX | SYSEX_END) {
+            // Part of a message with dropped status byte.
+            return Ok(None);
+        }
+
+        let action = filter(byte);
+
+        if (STATUS_CHANNEL_MIN..=SYSTEM_COMMON_MAX).contains(&byte) {
+            self.filter_dropping = action == FilterAction::Drop;
+        }
+
+        match action {
+            FilterAction::Pass => self.parse(byte),
+            FilterAction::Drop => Ok(None),
+            FilterAction::Replace(byte) => self.parse(byte),
+        }
+    }
+
+    /// Feed a byte received at `timestamp` into the parser like [`parse`](Self::parse),
+    /// applying [`ParserConfig::stale_message_timeout`]. Timestamps may wrap around.
+    #[must_use = "the completed message is lost if it is not used"]
+    pub fn parse_timed(&mut self, byte: u8, timestamp: u32) -> Result<Option<&[u8]>, ParserError> {
+        if byte < REALTIME_MIN {
+            if let Some(timeout) = self.config.stale_message_timeout {
+                let stale = timestamp.wrapping_sub(self.last_timestamp) > timeout;
+                if stale && !self.sysex_running && self.mid_message() {
+                    self.message_length = 0;
+                    self.status_received = false;
+                }
+            }
+            self.last_timestamp = timestamp;
+        }
+
+        self.parse(byte)
+    }
+
+    /// Feed a byte into the parser like [`parse`](Self::parse) and pass it unchanged to
+    /// `echo`, e.g. for a byte-accurate MIDI thru path. The byte is echoed before parsing
+    /// unless [`ParserConfig::echo_after_parse`] is set, and even if parsing fails.
+    #[must_use = "the completed message is lost if it is not used"]
+    pub fn parse_and_echo(
+        &mut self,
+        byte: u8,
+        echo: &mut impl FnMut(u8),
+    ) -> Result<Option<&[u8]>, ParserError> {
+        let echo_after_parse = self.config.echo_after_parse;
+
+        if !echo_after_parse {
+            echo(byte);
+        }
+
+        let result = self.parse(byte);
+
+        if echo_after_parse {
+            echo(byte);
+        }
+
+        result
+    }
+
+    /// Feed a byte into the parser and return the result as typed message.
+    /// Works like [`parse`](Self::parse), but applies the message filter if one is set.
+    #[must_use = "the completed message is lost if it is not used"]
+    pub fn parse_typed(&mut self, byte: u8) -> Result<Option<MidiMessage<'_>>, ParserError> {
+        let filter = self.message_filter;
+
+        Ok(self
+            .parse(byte)?
+            .map(MidiMessage::decode)
+            .filter(|message| filter.map_or(true, |filter| filter.accepts(message))))
+    }
+
+    /// Feed a byte received at `timestamp` into the parser and return the completed
+    /// message as event carrying the timestamp. Works like [`parse_typed`](Self::parse_typed).
+    #[must_use = "the completed message is lost if it is not used"]
+    pub fn parse_event(
+        &mut self,
+        byte: u8,
+        timestamp: Option<u64>,
+    ) -> Result<Option<MidiEvent<'_>>, ParserError> {
+        Ok(self
+            .parse_typed(byte)?
+            .map(|message| MidiEvent { timestamp, message }))
+    }
+
+    /// Feed a byte into the parser and store a completed message in `out`.
+    ///
+    /// Returns `true` if `out` was written. Unlike [`parse_typed`](Self::parse_typed),
+    /// the message doesn't borrow the parser, so a single slot can be reused for the
+    /// whole stream. Messages referring to the parser buffer, such as SysEx, are reported
+    /// as [`ParserError::BorrowedMessage`] and leave `out` unchanged.
+    #[must_use = "the result tells if `out` was written"]
+    pub fn parse_into_msg(
+        &mut self,
+        byte: u8,
+        out: &mut MidiMessage<'static>,
+    ) -> Result<bool, ParserError> {
+        match self.parse_typed(byte)? {
+            Some(message) => {
+                *out = message.to_static().ok_or(ParserError::BorrowedMessage)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Feed a byte into the parser, streaming SysEx payload bytes into `sink`.
+    ///
+    /// SysEx data bytes are written to the sink as they arrive instead of being buffered,
+    /// so SysEx messages are not limited to `SYSEX_MAX_LEN` bytes. The end of SysEx calls
+    /// [`SysExSink::finish`] and is not returned. A status byte interrupting the SysEx
+    /// message calls [`SysExSink::abort`] before it is parsed. All other messages are
+    /// returned as by [`parse`](Self::parse).
+    ///
+    /// The first bytes of the message are kept in the SysEx buffer, so the end of SysEx is
+    /// handled as by [`parse`](Self::parse), including the device ID filter, which calls
+    /// [`SysExSink::abort`] instead of [`SysExSink::finish`] for messages to other devices.
+    /// This requires `SYSEX_MAX_LEN` to be at least 3.
+    #[must_use = "the completed message is lost if it is not used"]
+    pub fn parse_to_sink(
+        &mut self,
+        byte: u8,
+        sink: &mut impl SysExSink,
+    ) -> Result<Option<&[u8]>, ParserError> {
+        match byte {
+            0x00..=0x7F if self.sysex_running => {
+                if !self.config.sysex_ring_buffer
+                    && self.sysex_message_length < SYSEX_MAX_LEN.min(SYSEX_SINK_HEAD_LEN)
+                {
+                    // Start of the message for the checks on the end of SysEx.
+                    self.sysex_message[self.sysex_message_length] = byte;
+                    self.sysex_message_length += 1;
+                }
+                sink.write(&[byte]).map_err(ParserError::Sink)?;
+                Ok(None)
+            }
+            0xF7 if self.sysex_running => {
+                let empty = SYSEX_MAX_LEN >= 2 && self.sysex_message_length == 1;
+                let accepted = self.end_sysex(empty);
+                self.sysex_message_length = 0;
+                self.sysex_ring_start = 0;
+                if accepted? {
+                    sink.finish().map_err(ParserError::Sink)?;
+                } else {
+                    sink.abort();
+                }
+                Ok(None)
+            }
+            0x80..=0xF6 if self.sysex_running && self.config.spec_compliant => {
+                sink.abort();
+                self.parse(byte)
+            }
+            _ => self.parse(byte),
+        }
+    }
+
+    /// Feed a byte into the parser and push all messages produced by it into `out`.
+    ///
+    /// Without [`ParserConfig::sysex_ring_buffer`], a byte completes at most one message,
+    /// so this is equivalent to [`parse_typed`](Self::parse_typed). In ring buffer mode,
+    /// the end of SysEx byte drains the bytes not taken yet as one or two
+    /// [`MidiMessage::SysExChunk`] messages, followed by [`MidiMessage::SysExEnd`].
+    ///
+    /// The messages borrow the parser, so they must be processed before the next byte is fed.
+    pub fn feed<'a>(
+        &'a mut self,
+        byte: u8,
+        out: &mut impl Extend<MidiMessage<'a>>,
+    ) -> Result<(), ParserError> {
+        if !(self.config.sysex_ring_buffer && self.sysex_running && byte == 0xF7) {
+            if let Some(message) = self.parse_typed(byte)? {
+                out.extend(core::iter::once(message));
+            }
+            return Ok(());
+        }
+
+        self.parse(byte)?;
+
+        // Drain the ring buffer, which may wrap around.
+        let start = self.sysex_ring_start;
+        let length = self.sysex_message_length;
+        self.sysex_ring_start = 0;
+        self.sysex_message_length = 0;
+
+        let this: &'a Self = self;
+        let first_end = (start + length).min(SYSEX_MAX_LEN);
+        let first = &this.sysex_message[start..first_end];
+        let second = &this.sysex_message[..length - first.len()];
+
+        out.extend(
+            [first, second]
+                .iter()
+                .filter(|chunk| !chunk.is_empty())
+                .map(|chunk| MidiMessage::SysExChunk(chunk))
+                .chain(core::iter::once(MidiMessage::SysExEnd)),
+        );
+
+        Ok(())
+    }
+
+    /// Feed a byte into the parser and route a completed message to one of the closures.
+    /// System realtime messages are passed as status byte to `on_realtime`,
+    /// all other messages to `on_message`.
+    pub fn parse_split(
+        &mut self,
+        byte: u8,
+        mut on_realtime: impl FnMut(u8),
+        mut on_message: impl FnMut(&[u8]),
+    ) -> Result<(), ParserError> {
+        if let Some(message) = self.parse(byte)? {
+            if message[0] >= 0xF8 {
+                on_realtime(message[0]);
+            } else {
+                on_message(message);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Feed all bytes into the parser and return the number of completed messages.
+    /// Parsing stops at the first error.
+    #[must_use = "the number of messages is the only result"]
+    pub fn count_messages(&mut self, bytes: &[u8]) -> Result<usize, ParserError> {
+        let mut count = 0;
+
+        for byte in bytes {
+            if self.parse(*byte)?.is_some() {
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Returns a snapshot of the current state that can be restored later.
+    /// The configuration is not part of the snapshot.
+    pub fn snapshot(&self) -> ParserState<SYSEX_MAX_LEN> {
+        ParserState {
+            message: self.message,
+            message_length: self.message_length,
+            realtime_message: self.realtime_message,
+            sysex_running: self.sysex_running,
+            sysex_message: self.sysex_message,
+            sysex_message_length: self.sysex_message_length,
+            sysex_ring_start: self.sysex_ring_start,
+            status_received: self.status_received,
+            running_status_used: self.running_status_used,
+            warmed_up: self.warmed_up,
+            sysex_discarding: self.sysex_discarding,
+            sysex_truncated: self.sysex_truncated,
+            sysex_saved_status: self.sysex_saved_status,
+            sysex_fragment_length: self.sysex_fragment_length,
+        }
+    }
+
+    /// Restore a state previously taken with [`snapshot`](Self::snapshot).
+    /// Only the used part of the SysEx buffer is copied back.
+    pub fn restore(&mut self, state: &ParserState<SYSEX_MAX_LEN>) {
+        self.message = state.message;
+        self.message_length = state.message_length;
+        self.message_expected_length = decode_status(state.message[0]).length.unwrap_or(1);
+        self.realtime_message = state.realtime_message;
+        self.sysex_running = state.sysex_running;
+        self.sysex_message_length = state.sysex_message_length;
+        self.sysex_ring_start = state.sysex_ring_start;
+        self.status_received = state.status_received;
+        self.running_status_used = state.running_status_used;
+        self.warmed_up = state.warmed_up;
+        self.sysex_discarding = state.sysex_discarding;
+        self.sysex_truncated = state.sysex_truncated;
+        self.sysex_saved_status = state.sysex_saved_status;
+        self.last_message = LastMessage::None;
+        self.sysex_fragment_length = state.sysex_fragment_length;
+        let used = if self.config.sysex_ring_buffer {
+            SYSEX_MAX_LEN
+        } else {
+            state.sysex_message_length.max(state.sysex_fragment_length)
+        };
+        self.sysex_message[..used].copy_from_slice(&state.sysex_message[..used]);
+    }
+
+    /// Write the state of a SysEx message in progress into `out` in a compact format and
+    /// return the number of bytes written, or 0 if `out` is too small.
+    /// Restore it with [`load_sysex_state`](Self::load_sysex_state), e.g. after a reboot.
+    ///
+    /// The format consists of a byte that is 1 if a SysEx message is in progress, the number
+    /// of buffered bytes as 32-bit little endian value and the buffered bytes themselves.
+    pub fn serialize_sysex_state(&self, out: &mut [u8]) -> usize {
+        let length = self.sysex_message_length;
+        let total = SYSEX_STATE_HEADER_LEN + length;
+
+        if out.len() < total || length > u32::MAX as usize {
+            return 0;
+        }
+
+        out[0] = self.sysex_running as u8;
+        out[1..SYSEX_STATE_HEADER_LEN].copy_from_slice(&(length as u32).to_le_bytes());
+        for (i, byte) in out[SYSEX_STATE_HEADER_LEN..total].iter_mut().enumerate() {
+            *byte = self.sysex_message[(self.sysex_ring_start + i) % SYSEX_MAX_LEN];
+        }
+
+        total
+    }
+
+    /// Restore the SysEx state written by [`serialize_sysex_state`](Self::serialize_sysex_state).
+    /// A message in progress is continued with the next byte fed.
+    pub fn load_sysex_state(&mut self, bytes: &[u8]) -> Result<(), ParserError> {
+        if bytes.len() < SYSEX_STATE_HEADER_LEN || bytes[0] > 1 {
+            return Err(ParserError::InvalidLength);
+        }
+
+        let mut length = [0; 4];
+        length.copy_from_slice(&bytes[1..SYSEX_STATE_HEADER_LEN]);
+        let length = u32::from_le_bytes(length) as usize;
+
+        if length > SYSEX_MAX_LEN || bytes.len() != SYSEX_STATE_HEADER_LEN + length {
+            return Err(ParserError::InvalidLength);
+        }
+
+        self.sysex_running = bytes[0] == 1;
+        self.sysex_message[..length].copy_from_slice(&bytes[SYSEX_STATE_HEADER_LEN..]);
+        self.sysex_message_length = length;
+        self.sysex_ring_start = 0;
+        self.sysex_fragment_length = 0;
+        self.last_message = LastMessage::None;
+        if self.sysex_running {
+            self.message_length = 0;
+            self.warmed_up = true;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the last completed channel message reused the status byte
+    /// of a previous message instead of carrying its own.
+    pub fn last_used_running_status(&self) -> bool {
+        self.running_status_used
+    }
+
+    /// Returns `true` if the last completed SysEx message was closed at the end of the
+    /// buffer, see [`ParserConfig::truncate_sysex_on_overflow`].
+    pub fn last_sysex_truncated(&self) -> bool {
+        self.sysex_truncated
+    }
+
+    /// Returns `true` once a status byte other than realtime has been received
+    /// since creation or the last [`reset`](Self::reset).
+    pub fn warmed_up(&self) -> bool {
+        self.warmed_up
+    }
+
+    /// Returns the number of bytes buffered for the message in progress.
+    /// This includes a status byte kept for running status.
+    pub fn pending_len(&self) -> usize {
+        if self.sysex_running {
+            self.sysex_message_length
+        } else {
+            self.message_length
+        }
+    }
+
+    /// Returns the size of the SysEx buffer, which is `SYSEX_MAX_LEN`.
+    pub const fn capacity(&self) -> usize {
+        SYSEX_MAX_LEN
+    }
+
+    /// Returns the largest number of bytes the SysEx buffer held so far,
+    /// including the start and end of SysEx bytes. Not cleared by [`reset`](Self::reset),
+    /// so it can be used to find a suitable `SYSEX_MAX_LEN` for a device.
+    pub fn high_water_mark(&self) -> usize {
+        self.sysex_high_water_mark
+    }
+
+    /// Returns `true` if a message has been started but not completed yet, i.e. a SysEx
+    /// message is running or further data bytes are expected after a status byte or
+    /// some data bytes. A status byte only kept for running status doesn't count.
+    pub fn mid_message(&self) -> bool {
+        self.sysex_running
+            || self.message_length > 1
+            || (self.message_length == 1 && self.status_received)
+    }
+
+    /// Returns `true` if no message is in progress and no running status is active.
+    pub fn is_idle(&self) -> bool {
+        !self.sysex_running && self.message_length == 0
+    }
+
+    /// Start a message with a status byte that is followed by data bytes.
+    fn set_status(&mut self, status: u8) {
+        self.message[0] = status;
+        self.message_length = 1;
+        self.message_expected_length = decode_status(status).length.unwrap_or(1);
+    }
+
+    /// Restore the running status saved at the start of SysEx, if `always` is set or
+    /// it is enabled in the configuration.
+    fn restore_running_status(&mut self, always: bool) {
+        if self.sysex_saved_status != 0
+            && (always || self.config.preserve_running_status_across_sysex)
+        {
+            self.set_status(self.sysex_saved_status);
+            self.status_received = false;
+        }
+        self.sysex_saved_status = 0;
+    }
+
+    /// Finish the SysEx message in progress on an end of SysEx byte that is not buffered
+    /// yet and restore the running status. In strict mode, an `empty` message is reported
+    /// as [`ParserError::EmptySysEx`]. Returns `false` if the message is dropped by the
+    /// device ID filter.
+    fn end_sysex(&mut self, empty: bool) -> Result<bool, ParserError> {
+        // Too short to continue a retained fragment.
+        self.sysex_fragment_length = 0;
+        self.sysex_running = false;
+        self.restore_running_status(false);
+
+        if self.config.strict && !self.config.sysex_ring_buffer && empty {
+            // No payload between start and end of SysEx.
+            return Err(ParserError::EmptySysEx);
+        }
+
+        Ok(self.sysex_device_id_matches())
+    }
+
+    /// Returns the parsing state a message in progress may change.
+    fn dma_boundary(&self) -> DmaBoundary {
+        let mut sysex_fragment_start = [0; 4];
+        let length = SYSEX_MAX_LEN.min(sysex_fragment_start.len());
+        sysex_fragment_start[..length].copy_from_slice(&self.sysex_message[..length]);
+
+        DmaBoundary {
+            message: self.message,
+            message_length: self.message_length,
+            message_expected_length: self.message_expected_length,
+            status_received: self.status_received,
+            warmed_up: self.warmed_up,
+            sysex_running: self.sysex_running,
+            sysex_message_length: self.sysex_message_length,
+            sysex_ring_start: self.sysex_ring_start,
+            sysex_discarding: self.sysex_discarding,
+            sysex_saved_status: self.sysex_saved_status,
+            sysex_fragment_length: self.sysex_fragment_length,
+            sysex_warned: self.sysex_warned,
+            sysex_fragment_start,
+        }
+    }
+
+    /// Restore the parsing state returned by [`dma_boundary`](Self::dma_boundary),
+    /// discarding the message in progress. A retained fragment is kept unless it was
+    /// overwritten.
+    fn restore_dma_boundary(&mut self, boundary: &DmaBoundary) {
+        let length = SYSEX_MAX_LEN.min(boundary.sysex_fragment_start.len());
+        let fragment_intact =
+            self.sysex_message[..length] == boundary.sysex_fragment_start[..length];
+
+        self.message = boundary.message;
+        self.message_length = boundary.message_length;
+        self.message_expected_length = boundary.message_expected_length;
+        self.status_received = boundary.status_received;
+        self.warmed_up = boundary.warmed_up;
+        self.sysex_running = boundary.sysex_running;
+        self.sysex_message_length = boundary.sysex_message_length;
+        self.sysex_ring_start = boundary.sysex_ring_start;
+        self.sysex_discarding = boundary.sysex_discarding;
+        self.sysex_saved_status = boundary.sysex_saved_status;
+        self.sysex_fragment_length = if fragment_intact {
+            boundary.sysex_fragment_length
+        } else {
+            0
+        };
+        self.sysex_warned = boundary.sysex_warned;
+    }
+
+    /// Returns an iterator over the bytes in use of the SysEx buffer, in ring buffer
+    /// order and including a retained fragment or the SysEx message returned last.
+    fn used_sysex_bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        let last_length = match self.last_message {
+            LastMessage::SysEx(length) => length,
+            _ => 0,
+        };
+        let length = self
+            .sysex_message_length
+            .max(self.sysex_fragment_length)
+            .max(last_length);
+
+        (0..length)
+            .map(move |index| self.sysex_message[(self.sysex_ring_start + index) % SYSEX_MAX_LEN])
+    }
+
+    /// Returns the channel status byte in use for running status.
+    fn running_status(&self) -> Option<u8> {
+        if self.message_length > 0 && self.message[0] <= STATUS_CHANNEL_MAX {
+            Some(self.message[0])
+        } else {
+            None
+        }
+    }
+
+    /// Finish a channel message, keeping the status byte for running status.
+    fn complete_channel_message(&mut self) {
+        self.message_length = 1;
+        self.running_status_used = !self.status_received;
+        self.status_received = false;
+    }
+
+    /// Returns `true` if the SysEx message in the buffer passes the device ID filter.
+    fn sysex_device_id_matches(&self) -> bool {
+        match (self.config.sysex_device_id, &self.sysex_message[..]) {
+            (Some(device_id), [0xF0, 0x7E | 0x7F, target, ..])
+                if self.sysex_message_length >= 3 =>
+            {
+                *target == device_id || *target == 0x7F
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns `true` if the SysEx buffer is full and the message is to be truncated.
+    fn sysex_truncation_pending(&self) -> bool {
+        self.config.truncate_sysex_on_overflow
+            && !self.config.sysex_ring_buffer
+            && SYSEX_MAX_LEN >= 2
+            && self.sysex_message_length >= SYSEX_MAX_LEN
+    }
+
+    /// Close the SysEx message by replacing the last byte of the buffer with `0xF7`.
+    fn truncate_sysex(&mut self) -> &[u8] {
+        self.sysex_fragment_length = 0;
+        self.sysex_truncated = true;
+        self.sysex_message[SYSEX_MAX_LEN - 1] = 0xF7;
+        &self.sysex_message[..SYSEX_MAX_LEN]
+    }
+
+    /// Append a byte to the SysEx buffer.
+    fn push_sysex(&mut self, byte: u8) -> Result<(), ParserError> {
+        if self.sysex_message_length >= SYSEX_MAX_LEN {
+            return Err(ParserError::SysexOverflow);
+        }
+        let position = (self.sysex_ring_start + self.sysex_message_length) % SYSEX_MAX_LEN;
+        self.sysex_message[position] = byte;
+        self.sysex_message_length += 1;
+        self.sysex_length_changed();
+
+        Ok(())
+    }
+
+    /// Track the SysEx buffer usage after a byte was added.
+    fn sysex_length_changed(&mut self) {
+        self.sysex_high_water_mark = self.sysex_high_water_mark.max(self.sysex_message_length);
+        let threshold = self.config.sysex_warn_threshold;
+        if threshold > 0 && !self.sysex_warned && self.sysex_message_length >= threshold {
+            self.sysex_warned = true;
+            if let Some(Callback(hook)) = self.sysex_warning {
+                hook(self.sysex_message_length);
+            }
+        }
+    }
+
+    /// Keep the SysEx message in progress as fragment if a continuation hook is set
+    /// and it contains at least the manufacturer ID.
+    fn retain_sysex_fragment(&mut self) {
+        if self.sysex_continuation.is_some()
+            && !self.config.sysex_ring_buffer
+            && self.sysex_fragment_length == 0
+            && self.sysex_message_length > self.sysex_manufacturer_id_length()
+        {
+            self.sysex_fragment_length = self.sysex_message_length;
+        }
+    }
+
+    /// Compare a SysEx data byte with the manufacturer ID of the retained fragment
+    /// and return `true` if it was consumed as part of the ID.
+    fn match_sysex_fragment(&mut self, byte: u8) -> bool {
+        let position = self.sysex_message_length;
+
+        if position >= self.sysex_fragment_length || byte != self.sysex_message[position] {
+            // Different manufacturer, continue as new message.
+            self.sysex_fragment_length = 0;
+            return false;
+        }
+
+        self.sysex_message_length += 1;
+
+        if position == self.sysex_manufacturer_id_length() {
+            // Manufacturer ID complete, ask the hook.
+            let fragment = &self.sysex_message[..self.sysex_fragment_length];
+            if let Some(Callback(hook)) = self.sysex_continuation {
+                if hook(fragment) {
+                    self.sysex_message_length = self.sysex_fragment_length;
+                    self.sysex_length_changed();
+                }
+            }
+            self.sysex_fragment_length = 0;
+        }
+
+        true
+    }
+
+    /// Returns the length of the manufacturer ID in the SysEx buffer,
+    /// 3 bytes for extended IDs starting with 0, 1 byte otherwise.
+    fn sysex_manufacturer_id_length(&self) -> usize {
+        if self.sysex_message_length > 1 && self.sysex_message[1] == 0x00 {
+            3
+        } else {
+            1
+        }
+    }
+
+    /// Check the consistency of the internal state.
+    /// Intended for tests and fuzzing, does nothing in release builds.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if an invariant is violated.
+    pub fn check_invariants(&self) {
+        debug_assert!(self.message_length < 3, "message length out of range");
+        debug_assert!(
+            self.message_length == 0 || matches!(self.message[0], 0x80..=0xEF | 0xF1..=0xF3),
+            "running status without valid status byte"
+        );
+        debug_assert!(
+            self.sysex_message_length <= SYSEX_MAX_LEN,
+            "SysEx length exceeds buffer"
+        );
+        debug_assert!(
+            self.sysex_ring_start == 0 || self.sysex_ring_start < SYSEX_MAX_LEN,
+            "SysEx ring buffer position out of range"
+        );
+        debug_assert!(
+            self.sysex_fragment_length <= SYSEX_MAX_LEN,
+            "SysEx fragment length exceeds buffer"
+        );
+        debug_assert!(
+            !self.sysex_running
+                || SYSEX_MAX_LEN == 0
+                || self.config.sysex_ring_buffer
+                || (self.sysex_message_length > 0 && self.sysex_message[0] == 0xF0),
+            "SysEx running without start byte"
+        );
+    }
+
+    /// Returns if a channel or system common message is in progress that still waits
+    /// for data bytes. A status byte only kept for running status doesn't count.
+    fn is_incomplete(&self) -> bool {
+        !self.sysex_running && self.mid_message()
+    }
+}
+
+/// Parser interface independent of the SysEx buffer size, allowing parsers of
+/// different sizes to be used as trait objects.
+pub trait MidiParse {
+    /// Feed a byte into the parser, see [`MidiStreamParser::parse`].
+    fn parse(&mut self, byte: u8) -> Result<Option<&[u8]>, ParserError>;
+
+    /// Reset the parsing state, see [`MidiStreamParser::reset`].
+    fn reset(&mut self);
+}
+
+impl<const SYSEX_MAX_LEN: usize> MidiParse for MidiStreamParser<SYSEX_MAX_LEN> {
+    fn parse(&mut self, byte: u8) -> Result<Option<&[u8]>, ParserError> {
+        MidiStreamParser::parse(self, byte)
+    }
+
+    fn reset(&mut self) {
+        MidiStreamParser::reset(self);
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,146 @@
+//! Rate limiter thinning out floods of continuous controller messages.
+
+use crate::MidiMessage;
+
+/// Rate limiter passing at most `max_messages` control change, pitch bend and pressure
+/// messages per interval. Thinned messages are kept in up to `SLOTS` pending slots, one per
+/// controller, holding the most recent value until it can be sent. All other messages
+/// pass untouched.
+///
+/// Timestamps are given in arbitrary units, e.g. milliseconds, and may wrap around.
+#[derive(Debug, Clone)]
+pub struct RateLimiter<const SLOTS: usize> {
+    /// Length of an interval.
+    interval: u32,
+
+    /// Maximum number of messages per interval.
+    max_messages: u16,
+
+    /// Timestamp of the start of the current interval.
+    interval_start: u32,
+
+    /// Number of messages passed in the current interval.
+    count: u16,
+
+    /// Most recent values of thinned controllers.
+    pending: [Option<MidiMessage<'static>>; SLOTS],
+}
+
+impl<const SLOTS: usize> RateLimiter<SLOTS> {
+    /// Returns a new rate limiter passing up to `max_messages` per `interval`.
+    pub fn new(max_messages: u16, interval: u32) -> Self {
+        Self {
+            interval,
+            max_messages,
+            interval_start: 0,
+            count: 0,
+            pending: [None; SLOTS],
+        }
+    }
+
+    /// Process a message received at `timestamp` and return `true` if it is to be sent.
+    ///
+    /// A thinned message replaces a pending message for the same controller and is returned
+    /// later by [`poll`](Self::poll). If all pending slots are occupied by other controllers,
+    /// the message is passed, so no most recent value gets lost.
+    pub fn process(&mut self, message: &MidiMessage, timestamp: u32) -> bool {
+        if !is_limited(message) {
+            return true;
+        }
+
+        self.update_interval(timestamp);
+
+        let slot = self.pending.iter().position(|pending| {
+            pending.map_or(false, |pending| same_controller(&pending, message))
+        });
+
+        if self.count < self.max_messages {
+            self.count += 1;
+            if let Some(slot) = slot {
+                // Superseded by the message to be sent.
+                self.pending[slot] = None;
+            }
+            return true;
+        }
+
+        match slot.or_else(|| self.pending.iter().position(Option::is_none)) {
+            Some(slot) => {
+                self.pending[slot] = message.to_static();
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Returns a pending message if the interval at `timestamp` allows sending it.
+    /// Call regularly until `None` is returned.
+    pub fn poll(&mut self, timestamp: u32) -> Option<MidiMessage<'static>> {
+        self.update_interval(timestamp);
+
+        if self.count >= self.max_messages {
+            return None;
+        }
+
+        let message = self.pending.iter_mut().find_map(Option::take)?;
+        self.count += 1;
+
+        Some(message)
+    }
+
+    /// Start a new interval if the current one has elapsed.
+    fn update_interval(&mut self, timestamp: u32) {
+        if timestamp.wrapping_sub(self.interval_start) >= self.interval {
+            self.interval_start = timestamp;
+            self.count = 0;
+        }
+    }
+}
+
+/// Returns `true` for messages subject to rate limiting.
+fn is_limited(message: &MidiMessage) -> bool {
+    matches!(
+        message,
+        MidiMessage::ControlChange { .. }
+            | MidiMessage::PitchBend { .. }
+            | MidiMessage::ChannelPressure { .. }
+            | MidiMessage::PolyPressure { .. }
+    )
+}
+
+/// Returns `true` if both messages address the same controller on the same channel.
+fn same_controller(a: &MidiMessage, b: &MidiMessage) -> bool {
+    match (*a, *b) {
+        (
+            MidiMessage::ControlChange {
+                channel: a_channel,
+                controller: a_controller,
+                ..
+            },
+            MidiMessage::ControlChange {
+                channel: b_channel,
+                controller: b_controller,
+                ..
+            },
+        ) => a_channel == b_channel && a_controller == b_controller,
+        (
+            MidiMessage::PolyPressure {
+                channel: a_channel,
+                note: a_note,
+                ..
+            },
+            MidiMessage::PolyPressure {
+                channel: b_channel,
+                note: b_note,
+                ..
+            },
+        ) => a_channel == b_channel && a_note == b_note,
+        (MidiMessage::PitchBend { .. }, MidiMessage::PitchBend { .. })
+        | (MidiMessage::ChannelPressure { .. }, MidiMessage::ChannelPressure { .. }) => {
+            a.channel() == b.channel()
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests;
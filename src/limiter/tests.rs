@@ -0,0 +1,66 @@
+//! Unit tests
+
+use super::*;
+
+/// Pitch bend burst thinned to the limit, keeping the most recent value.
+#[test]
+fn pitch_bend_burst() {
+    let mut limiter = RateLimiter::<4>::new(2, 10);
+
+    let passed: Vec<bool> = (0..6)
+        .map(|i| {
+            let message = MidiMessage::PitchBend {
+                channel: 0,
+                value: 8192 + i * 100,
+            };
+            limiter.process(&message, 1)
+        })
+        .collect();
+    assert_eq!(passed, [true, true, false, false, false, false]);
+
+    let note = MidiMessage::NoteOn {
+        channel: 0,
+        note: 60,
+        velocity: 100,
+    };
+    assert!(limiter.process(&note, 2));
+
+    assert_eq!(limiter.poll(5), None);
+    assert_eq!(
+        limiter.poll(11),
+        Some(MidiMessage::PitchBend {
+            channel: 0,
+            value: 8692
+        })
+    );
+    assert_eq!(limiter.poll(12), None);
+}
+
+/// Pending value superseded by a message sent in a new interval.
+#[test]
+fn superseded() {
+    let mut limiter = RateLimiter::<1>::new(1, 10);
+
+    let volume = |value| MidiMessage::ControlChange {
+        channel: 3,
+        controller: 7,
+        value,
+    };
+
+    assert!(limiter.process(&volume(10), 0));
+    assert!(!limiter.process(&volume(20), 1));
+    assert!(limiter.process(&volume(30), 10));
+    assert_eq!(limiter.poll(20), None);
+
+    assert!(limiter.process(&volume(40), 21));
+    assert!(!limiter.process(&volume(50), 22));
+
+    // No free slot for another controller.
+    let pan = MidiMessage::ControlChange {
+        channel: 3,
+        controller: 10,
+        value: 64,
+    };
+    assert!(limiter.process(&pan, 23));
+    assert_eq!(limiter.poll(31), Some(volume(50)));
+}
@@ -0,0 +1,396 @@
+//! Typed representation of MIDI messages.
+
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::{decode_status, ByteClass, ParserError, QuarterFrame, StatusInfo, SysEx};
+
+/// Decoded MIDI message.
+///
+/// Channels are stored as on the wire in the range 0-15.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MidiMessage<'a> {
+    /// Note off with note number and release velocity.
+    NoteOff { channel: u8, note: u8, velocity: u8 },
+
+    /// Note on with note number and velocity.
+    NoteOn { channel: u8, note: u8, velocity: u8 },
+
+    /// Polyphonic key pressure.
+    PolyPressure { channel: u8, note: u8, pressure: u8 },
+
+    /// Control change with controller number and value.
+    ControlChange {
+        channel: u8,
+        controller: u8,
+        value: u8,
+    },
+
+    /// Program change.
+    ProgramChange { channel: u8, program: u8 },
+
+    /// Channel pressure.
+    ChannelPressure { channel: u8, pressure: u8 },
+
+    /// Pitch bend with a 14-bit value, 8192 is the center position.
+    PitchBend { channel: u8, value: u16 },
+
+    /// System common message.
+    SystemCommon(SystemCommonMessage),
+
+    /// System realtime message.
+    SystemRealtime(SystemRealtimeMessage),
+
+    /// Start of a SysEx message, only emitted if enabled in the configuration.
+    SysExStart,
+
+    /// SysEx message including the start and end bytes.
+    SysEx(&'a [u8]),
+
+    /// Part of a SysEx message drained from the ring buffer.
+    SysExChunk(&'a [u8]),
+
+    /// End of a SysEx message drained from the ring buffer.
+    SysExEnd,
+}
+
+/// System common message variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemCommonMessage {
+    /// MIDI time code quarter frame with the piece number 0-7 and its 4-bit value.
+    QuarterFrame { message_type: u8, value: u8 },
+
+    /// Song position pointer in MIDI beats (sixteenth notes), 14-bit value.
+    SongPosition(u16),
+
+    /// Song select.
+    SongSelect(u8),
+
+    /// Tune request, only returned by the parser with
+    /// [`ParserConfig::spec_compliant`](crate::ParserConfig::spec_compliant).
+    TuneRequest,
+}
+
+/// System realtime message variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemRealtimeMessage {
+    /// Timing clock, sent 24 times per quarter note.
+    TimingClock,
+
+    /// Start the current sequence.
+    Start,
+
+    /// Continue at the point the sequence was stopped.
+    Continue,
+
+    /// Stop the current sequence.
+    Stop,
+
+    /// Active sensing.
+    ActiveSensing,
+
+    /// Reset all receivers to power-up state.
+    SystemReset,
+
+    /// Undefined status byte `0xF9` or `0xFD`.
+    Undefined(u8),
+}
+
+/// Channel mode message, sent as control change with controller numbers 120-127.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelModeMessage {
+    /// All sound off, controller 120.
+    AllSoundOff,
+
+    /// Reset all controllers, controller 121.
+    ResetAllControllers,
+
+    /// Local control on or off, controller 122.
+    LocalControl(bool),
+
+    /// All notes off, controller 123.
+    AllNotesOff,
+
+    /// Omni mode off, controller 124.
+    OmniOff,
+
+    /// Omni mode on, controller 125.
+    OmniOn,
+
+    /// Mono mode on with the number of channels, controller 126.
+    MonoOn(u8),
+
+    /// Poly mode on, controller 127.
+    PolyOn,
+}
+
+impl<'a> MidiMessage<'a> {
+    /// Returns the channel in the range 0-15 for channel voice messages.
+    pub fn channel(&self) -> Option<u8> {
+        match *self {
+            Self::NoteOff { channel, .. }
+            | Self::NoteOn { channel, .. }
+            | Self::PolyPressure { channel, .. }
+            | Self::ControlChange { channel, .. }
+            | Self::ProgramChange { channel, .. }
+            | Self::ChannelPressure { channel, .. }
+            | Self::PitchBend { channel, .. } => Some(channel),
+            _ => None,
+        }
+    }
+
+    /// Returns the channel in the range 1-16 for channel voice messages.
+    pub fn channel_1based(&self) -> Option<u8> {
+        self.channel().map(|channel| channel + 1)
+    }
+
+    /// Returns the channel and the channel mode message for control changes
+    /// with controller numbers 120-127.
+    pub fn channel_mode(&self) -> Option<(u8, ChannelModeMessage)> {
+        let (channel, controller, value) = match *self {
+            Self::ControlChange {
+                channel,
+                controller,
+                value,
+            } => (channel, controller, value),
+            _ => return None,
+        };
+
+        let message = match controller {
+            120 => ChannelModeMessage::AllSoundOff,
+            121 => ChannelModeMessage::ResetAllControllers,
+            122 => ChannelModeMessage::LocalControl(value >= 64),
+            123 => ChannelModeMessage::AllNotesOff,
+            124 => ChannelModeMessage::OmniOff,
+            125 => ChannelModeMessage::OmniOn,
+            126 => ChannelModeMessage::MonoOn(value),
+            127 => ChannelModeMessage::PolyOn,
+            _ => return None,
+        };
+
+        Some((channel, message))
+    }
+
+    /// Returns the SysEx wrapper for a [`MidiMessage::SysEx`] message.
+    pub fn as_sysex(&self) -> Option<SysEx<'a>> {
+        match *self {
+            Self::SysEx(bytes) => SysEx::new(bytes),
+            _ => None,
+        }
+    }
+
+    /// Decode a complete message as returned by the parser.
+    pub(crate) fn decode(bytes: &'a [u8]) -> Self {
+        let status = bytes[0];
+
+        match status {
+            0x80..=0xEF => {
+                Self::from_channel_voice(status, bytes[1], bytes.get(2).copied().unwrap_or(0))
+            }
+            0xF0 if bytes.len() == 1 => Self::SysExStart,
+            0xF0 => Self::SysEx(bytes),
+            0xF7 => Self::SysExEnd,
+            0xF8..=0xFF => Self::SystemRealtime(SystemRealtimeMessage::from(status)),
+            0xF1 => {
+                let QuarterFrame { piece, value } = QuarterFrame::from_data(bytes[1]);
+                Self::SystemCommon(SystemCommonMessage::QuarterFrame {
+                    message_type: piece,
+                    value,
+                })
+            }
+            0xF2 => Self::SystemCommon(SystemCommonMessage::SongPosition(
+                (bytes[2] as u16) << 7 | bytes[1] as u16,
+            )),
+            0xF3 => Self::SystemCommon(SystemCommonMessage::SongSelect(bytes[1])),
+            // Tune request, other system common messages are not returned by the parser.
+            _ => Self::SystemCommon(SystemCommonMessage::TuneRequest),
+        }
+    }
+
+    /// Returns the message with a `'static` lifetime or `None` if it borrows a buffer.
+    pub(crate) fn to_static(self) -> Option<MidiMessage<'static>> {
+        Some(match self {
+            Self::NoteOff {
+                channel,
+                note,
+                velocity,
+            } => MidiMessage::NoteOff {
+                channel,
+                note,
+                velocity,
+            },
+            Self::NoteOn {
+                channel,
+                note,
+                velocity,
+            } => MidiMessage::NoteOn {
+                channel,
+                note,
+                velocity,
+            },
+            Self::PolyPressure {
+                channel,
+                note,
+                pressure,
+            } => MidiMessage::PolyPressure {
+                channel,
+                note,
+                pressure,
+            },
+            Self::ControlChange {
+                channel,
+                controller,
+                value,
+            } => MidiMessage::ControlChange {
+                channel,
+                controller,
+                value,
+            },
+            Self::ProgramChange { channel, program } => {
+                MidiMessage::ProgramChange { channel, program }
+            }
+            Self::ChannelPressure { channel, pressure } => {
+                MidiMessage::ChannelPressure { channel, pressure }
+            }
+            Self::PitchBend { channel, value } => MidiMessage::PitchBend { channel, value },
+            Self::SystemCommon(message) => MidiMessage::SystemCommon(message),
+            Self::SystemRealtime(message) => MidiMessage::SystemRealtime(message),
+            Self::SysExStart => MidiMessage::SysExStart,
+            Self::SysExEnd => MidiMessage::SysExEnd,
+            Self::SysEx(_) | Self::SysExChunk(_) => return None,
+        })
+    }
+
+    /// Decode a channel voice message from its status and data bytes.
+    /// `data2` is ignored for 2-byte messages.
+    pub(crate) fn from_channel_voice(status: u8, data1: u8, data2: u8) -> Self {
+        let channel = status & 0x0F;
+
+        match status & 0xF0 {
+            0x80 => Self::NoteOff {
+                channel,
+                note: data1,
+                velocity: data2,
+            },
+            0x90 => Self::NoteOn {
+                channel,
+                note: data1,
+                velocity: data2,
+            },
+            0xA0 => Self::PolyPressure {
+                channel,
+                note: data1,
+                pressure: data2,
+            },
+            0xB0 => Self::ControlChange {
+                channel,
+                controller: data1,
+                value: data2,
+            },
+            0xC0 => Self::ProgramChange {
+                channel,
+                program: data1,
+            },
+            0xD0 => Self::ChannelPressure {
+                channel,
+                pressure: data1,
+            },
+            _ => Self::PitchBend {
+                channel,
+                value: (data2 as u16) << 7 | data1 as u16,
+            },
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for MidiMessage<'a> {
+    type Error = ParserError;
+
+    /// Decode a single complete message, validating status byte, length and data bytes.
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let status = *bytes.first().ok_or(ParserError::InvalidLength)?;
+
+        let length = match decode_status(status) {
+            StatusInfo {
+                length: Some(length),
+                ..
+            } => length,
+            StatusInfo {
+                class: ByteClass::SysExStart,
+                ..
+            } => {
+                if bytes.len() < 2 || bytes[bytes.len() - 1] != 0xF7 {
+                    return Err(ParserError::InvalidLength);
+                }
+                bytes.len()
+            }
+            _ => return Err(ParserError::InvalidStatus),
+        };
+
+        if bytes.len() != length {
+            return Err(ParserError::InvalidLength);
+        }
+
+        let data_end = if status == 0xF0 { length - 1 } else { length };
+        if bytes[1..data_end].iter().any(|byte| *byte > 0x7F) {
+            return Err(ParserError::InvalidData);
+        }
+
+        Ok(Self::decode(bytes))
+    }
+}
+
+impl From<u8> for SystemRealtimeMessage {
+    /// Returns the message for a status byte in the range `0xF8..=0xFF`.
+    fn from(status: u8) -> Self {
+        match status {
+            0xF8 => Self::TimingClock,
+            0xFA => Self::Start,
+            0xFB => Self::Continue,
+            0xFC => Self::Stop,
+            0xFE => Self::ActiveSensing,
+            0xFF => Self::SystemReset,
+            _ => Self::Undefined(status),
+        }
+    }
+}
+
+/// Decoded message with the timestamp of its completing byte,
+/// see [`MidiStreamParser::parse_event`](crate::MidiStreamParser::parse_event).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MidiEvent<'a> {
+    /// Timestamp in ticks of the byte completing the message, if known.
+    pub timestamp: Option<u64>,
+
+    /// Decoded message.
+    pub message: MidiMessage<'a>,
+}
+
+/// Filter for typed messages, see [`MidiStreamParser::set_message_filter`](crate::MidiStreamParser::set_message_filter).
+#[derive(Clone, Copy)]
+pub struct MessageFilter {
+    /// Predicate returning `true` for messages to keep.
+    pub predicate: fn(&MidiMessage) -> bool,
+
+    /// Keep system realtime messages without calling the predicate.
+    pub exempt_realtime: bool,
+}
+
+impl MessageFilter {
+    /// Returns `true` if the message passes the filter.
+    pub fn accepts(&self, message: &MidiMessage) -> bool {
+        (self.exempt_realtime && matches!(message, MidiMessage::SystemRealtime(_)))
+            || (self.predicate)(message)
+    }
+}
+
+impl fmt::Debug for MessageFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageFilter")
+            .field("exempt_realtime", &self.exempt_realtime)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,209 @@
+//! Unit tests
+
+use super::*;
+use core::convert::TryFrom;
+
+/// Decoding of channel voice messages.
+#[test]
+fn decode_channel_voice() {
+    assert_eq!(
+        MidiMessage::decode(&[0x93, 60, 100]),
+        MidiMessage::NoteOn {
+            channel: 3,
+            note: 60,
+            velocity: 100
+        }
+    );
+    assert_eq!(
+        MidiMessage::decode(&[0x80, 60, 0]),
+        MidiMessage::NoteOff {
+            channel: 0,
+            note: 60,
+            velocity: 0
+        }
+    );
+    assert_eq!(
+        MidiMessage::decode(&[0xBF, 7, 127]),
+        MidiMessage::ControlChange {
+            channel: 15,
+            controller: 7,
+            value: 127
+        }
+    );
+    assert_eq!(
+        MidiMessage::decode(&[0xC1, 5]),
+        MidiMessage::ProgramChange {
+            channel: 1,
+            program: 5
+        }
+    );
+    assert_eq!(
+        MidiMessage::decode(&[0xE0, 0x00, 0x40]),
+        MidiMessage::PitchBend {
+            channel: 0,
+            value: 8192
+        }
+    );
+}
+
+/// Decoding of system messages.
+#[test]
+fn decode_system() {
+    assert_eq!(
+        MidiMessage::decode(&[0xF0, 0x10, 0xF7]),
+        MidiMessage::SysEx(&[0xF0, 0x10, 0xF7])
+    );
+    assert_eq!(
+        MidiMessage::decode(&[0xF3, 0x05]),
+        MidiMessage::SystemCommon(SystemCommonMessage::SongSelect(5))
+    );
+    assert_eq!(
+        MidiMessage::decode(&[0xF8]),
+        MidiMessage::SystemRealtime(SystemRealtimeMessage::TimingClock)
+    );
+    assert_eq!(
+        MidiMessage::decode(&[0xFD]),
+        MidiMessage::SystemRealtime(SystemRealtimeMessage::Undefined(0xFD))
+    );
+}
+
+/// Raw channel stays 0-based, accessor returns 1-based channel.
+#[test]
+fn channel_numbering() {
+    let message = MidiMessage::decode(&[0x9F, 60, 100]);
+
+    assert!(matches!(message, MidiMessage::NoteOn { channel: 15, .. }));
+    assert_eq!(message.channel(), Some(15));
+    assert_eq!(message.channel_1based(), Some(16));
+
+    let message = MidiMessage::decode(&[0xC0, 5]);
+    assert_eq!(message.channel_1based(), Some(1));
+
+    let message = MidiMessage::decode(&[0xF8]);
+    assert_eq!(message.channel(), None);
+    assert_eq!(message.channel_1based(), None);
+}
+
+/// Decoding of single pre-framed messages.
+#[test]
+fn try_from_slice() {
+    assert_eq!(
+        MidiMessage::try_from([0x90, 60, 100].as_ref()),
+        Ok(MidiMessage::NoteOn {
+            channel: 0,
+            note: 60,
+            velocity: 100
+        })
+    );
+    assert_eq!(
+        MidiMessage::try_from([0xF0, 0x41, 0x10, 0xF7].as_ref()),
+        Ok(MidiMessage::SysEx(&[0xF0, 0x41, 0x10, 0xF7]))
+    );
+    assert_eq!(
+        MidiMessage::try_from([0xF6].as_ref()),
+        Ok(MidiMessage::SystemCommon(SystemCommonMessage::TuneRequest))
+    );
+
+    assert_eq!(
+        MidiMessage::try_from([0x90, 60].as_ref()),
+        Err(ParserError::InvalidLength)
+    );
+    assert_eq!(
+        MidiMessage::try_from([0xC0, 5, 6].as_ref()),
+        Err(ParserError::InvalidLength)
+    );
+    assert_eq!(
+        MidiMessage::try_from([].as_ref()),
+        Err(ParserError::InvalidLength)
+    );
+    assert_eq!(
+        MidiMessage::try_from([0xF0, 0x41].as_ref()),
+        Err(ParserError::InvalidLength)
+    );
+    assert_eq!(
+        MidiMessage::try_from([60, 100].as_ref()),
+        Err(ParserError::InvalidStatus)
+    );
+    assert_eq!(
+        MidiMessage::try_from([0xF4].as_ref()),
+        Err(ParserError::InvalidStatus)
+    );
+    assert_eq!(
+        MidiMessage::try_from([0x90, 60, 0x80].as_ref()),
+        Err(ParserError::InvalidData)
+    );
+    assert_eq!(
+        MidiMessage::try_from([0xF0, 0x90, 0xF7].as_ref()),
+        Err(ParserError::InvalidData)
+    );
+}
+
+/// SysEx wrapper of SysEx messages only.
+#[test]
+fn as_sysex() {
+    let bytes = [0xF0, 0x41, 0x10, 0xF7];
+    let sysex = MidiMessage::SysEx(&bytes).as_sysex().unwrap();
+
+    assert_eq!(sysex.as_bytes(), bytes);
+    assert_eq!(MidiMessage::SysExEnd.as_sysex(), None);
+}
+
+/// Channel mode messages decoded from control changes.
+#[test]
+fn channel_mode() {
+    let modes = [
+        (120, 0, Some(ChannelModeMessage::AllSoundOff)),
+        (121, 0, Some(ChannelModeMessage::ResetAllControllers)),
+        (122, 127, Some(ChannelModeMessage::LocalControl(true))),
+        (122, 0, Some(ChannelModeMessage::LocalControl(false))),
+        (123, 0, Some(ChannelModeMessage::AllNotesOff)),
+        (126, 4, Some(ChannelModeMessage::MonoOn(4))),
+        (127, 0, Some(ChannelModeMessage::PolyOn)),
+        (119, 0, None),
+    ];
+
+    for (controller, value, mode) in modes {
+        let message = MidiMessage::ControlChange {
+            channel: 5,
+            controller,
+            value,
+        };
+        assert_eq!(message.channel_mode(), mode.map(|mode| (5, mode)));
+    }
+
+    assert_eq!(
+        MidiMessage::ProgramChange {
+            channel: 0,
+            program: 120
+        }
+        .channel_mode(),
+        None
+    );
+}
+
+/// Decoding of each system common message from the parser output.
+#[test]
+fn decode_system_common() {
+    let mut parser = crate::MidiStreamParser::<0>::with_config(crate::ParserConfig::strict());
+    let mut messages = Vec::new();
+
+    for byte in [0xF1, 0x35, 0xF2, 0x10, 0x02, 0xF3, 0x07, 0xF6] {
+        if let Some(message) = parser.parse_typed(byte).unwrap() {
+            messages.push(message.to_static().unwrap());
+        }
+    }
+
+    assert_eq!(
+        messages,
+        [
+            SystemCommonMessage::QuarterFrame {
+                message_type: 3,
+                value: 5
+            },
+            SystemCommonMessage::SongPosition(0x110),
+            SystemCommonMessage::SongSelect(7),
+            SystemCommonMessage::TuneRequest,
+        ]
+        .map(MidiMessage::SystemCommon)
+    );
+}
@@ -0,0 +1,186 @@
+//! Helpers for MIDI Polyphonic Expression (MPE).
+
+use crate::MidiMessage;
+
+/// Controller number used for the timbre dimension.
+const TIMBRE_CONTROLLER: u8 = 74;
+
+/// MPE zone with a master channel and a range of member channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MpeZone {
+    /// Channel for zone-wide messages.
+    master_channel: u8,
+
+    /// First member channel.
+    first_member: u8,
+
+    /// Last member channel.
+    last_member: u8,
+}
+
+impl MpeZone {
+    /// Returns a lower zone with master channel 0 and member channels starting at 1.
+    /// `members` is limited to 1-15.
+    pub fn lower(members: u8) -> Self {
+        let members = members.clamp(1, 15);
+        Self {
+            master_channel: 0,
+            first_member: 1,
+            last_member: members,
+        }
+    }
+
+    /// Returns an upper zone with master channel 15 and member channels ending at 14.
+    /// `members` is limited to 1-15.
+    pub fn upper(members: u8) -> Self {
+        let members = members.clamp(1, 15);
+        Self {
+            master_channel: 15,
+            first_member: 15 - members,
+            last_member: 14,
+        }
+    }
+
+    /// Returns the master channel.
+    pub fn master_channel(&self) -> u8 {
+        self.master_channel
+    }
+
+    /// Returns `true` if `channel` is a member channel of the zone.
+    pub fn is_member(&self, channel: u8) -> bool {
+        (self.first_member..=self.last_member).contains(&channel)
+    }
+}
+
+/// Note on a member channel together with its per-note expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MpeNote {
+    /// Member channel of the note.
+    pub channel: u8,
+
+    /// Note number.
+    pub note: u8,
+
+    /// Note on velocity.
+    pub velocity: u8,
+
+    /// 14-bit pitch bend of the channel, 8192 is the center position.
+    pub pitch_bend: u16,
+
+    /// Channel pressure.
+    pub pressure: u8,
+
+    /// Timbre from controller 74.
+    pub timbre: u8,
+
+    /// `false` if the note was released by this event.
+    pub active: bool,
+}
+
+/// Expression state of a single member channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ChannelState {
+    /// Note number and velocity of the sounding note.
+    note: Option<(u8, u8)>,
+
+    /// Pitch bend value.
+    pitch_bend: u16,
+
+    /// Channel pressure value.
+    pressure: u8,
+
+    /// Timbre value.
+    timbre: u8,
+}
+
+impl Default for ChannelState {
+    fn default() -> Self {
+        Self {
+            note: None,
+            pitch_bend: 8192,
+            pressure: 0,
+            timbre: 64,
+        }
+    }
+}
+
+/// Tracker combining notes with the expression messages of their member channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MpeTracker {
+    /// Zone configuration.
+    zone: MpeZone,
+
+    /// State of all channels, indexed by channel number.
+    channels: [ChannelState; 16],
+}
+
+impl MpeTracker {
+    /// Returns a new tracker for a zone.
+    pub fn new(zone: MpeZone) -> Self {
+        Self {
+            zone,
+            channels: [ChannelState::default(); 16],
+        }
+    }
+
+    /// Returns the zone configuration.
+    pub fn zone(&self) -> &MpeZone {
+        &self.zone
+    }
+
+    /// Feed a message into the tracker and return the affected note, if any.
+    /// Messages outside of the member channels are ignored.
+    pub fn process(&mut self, message: &MidiMessage) -> Option<MpeNote> {
+        let channel = match *message {
+            MidiMessage::NoteOn { channel, .. }
+            | MidiMessage::NoteOff { channel, .. }
+            | MidiMessage::PitchBend { channel, .. }
+            | MidiMessage::ChannelPressure { channel, .. }
+            | MidiMessage::ControlChange { channel, .. } => channel,
+            _ => return None,
+        };
+
+        if !self.zone.is_member(channel) {
+            return None;
+        }
+
+        let state = &mut self.channels[channel as usize];
+        let mut active = true;
+
+        match *message {
+            MidiMessage::NoteOn { note, velocity, .. } if velocity > 0 => {
+                state.note = Some((note, velocity));
+            }
+            MidiMessage::NoteOn { note, .. } | MidiMessage::NoteOff { note, .. } => {
+                match state.note {
+                    Some((sounding, _)) if sounding == note => active = false,
+                    _ => return None,
+                }
+            }
+            MidiMessage::PitchBend { value, .. } => state.pitch_bend = value,
+            MidiMessage::ChannelPressure { pressure, .. } => state.pressure = pressure,
+            MidiMessage::ControlChange {
+                controller, value, ..
+            } if controller == TIMBRE_CONTROLLER => state.timbre = value,
+            _ => return None,
+        }
+
+        let (note, velocity) = state.note?;
+        if !active {
+            state.note = None;
+        }
+
+        Some(MpeNote {
+            channel,
+            note,
+            velocity,
+            pitch_bend: state.pitch_bend,
+            pressure: state.pressure,
+            timbre: state.timbre,
+            active,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
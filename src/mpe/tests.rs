@@ -0,0 +1,80 @@
+//! Unit tests
+
+use super::*;
+
+/// Zone channel layout.
+#[test]
+fn zones() {
+    let lower = MpeZone::lower(7);
+    assert_eq!(lower.master_channel(), 0);
+    assert!(!lower.is_member(0));
+    assert!(lower.is_member(1));
+    assert!(lower.is_member(7));
+    assert!(!lower.is_member(8));
+
+    let upper = MpeZone::upper(3);
+    assert_eq!(upper.master_channel(), 15);
+    assert!(!upper.is_member(11));
+    assert!(upper.is_member(12));
+    assert!(upper.is_member(14));
+    assert!(!upper.is_member(15));
+}
+
+/// Note on followed by pitch bend on the same member channel.
+#[test]
+fn note_with_pitch_bend() {
+    let mut tracker = MpeTracker::new(MpeZone::lower(15));
+
+    let note = tracker.process(&MidiMessage::NoteOn {
+        channel: 2,
+        note: 60,
+        velocity: 100,
+    });
+    assert_eq!(
+        note,
+        Some(MpeNote {
+            channel: 2,
+            note: 60,
+            velocity: 100,
+            pitch_bend: 8192,
+            pressure: 0,
+            timbre: 64,
+            active: true,
+        })
+    );
+
+    let note = tracker.process(&MidiMessage::PitchBend {
+        channel: 2,
+        value: 9000,
+    });
+    assert_eq!(
+        note.map(|note| (note.note, note.pitch_bend)),
+        Some((60, 9000))
+    );
+
+    let note = tracker.process(&MidiMessage::NoteOff {
+        channel: 2,
+        note: 60,
+        velocity: 0,
+    });
+    assert_eq!(note.map(|note| note.active), Some(false));
+
+    let note = tracker.process(&MidiMessage::PitchBend {
+        channel: 2,
+        value: 8192,
+    });
+    assert_eq!(note, None);
+}
+
+/// Messages on the master channel are not tracked.
+#[test]
+fn master_channel_ignored() {
+    let mut tracker = MpeTracker::new(MpeZone::lower(15));
+
+    let note = tracker.process(&MidiMessage::NoteOn {
+        channel: 0,
+        note: 60,
+        velocity: 100,
+    });
+    assert_eq!(note, None);
+}
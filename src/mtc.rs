@@ -0,0 +1,134 @@
+//! Accumulator for MIDI Time Code (MTC) quarter frame messages.
+
+/// Frame rate of a timecode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameRate {
+    /// 24 frames per second.
+    Fps24,
+
+    /// 25 frames per second.
+    Fps25,
+
+    /// 29.97 frames per second, drop frame.
+    Fps30Drop,
+
+    /// 30 frames per second.
+    Fps30,
+}
+
+/// Timecode assembled from 8 quarter frame messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MtcTimecode {
+    /// Hours 0-23.
+    pub hours: u8,
+
+    /// Minutes 0-59.
+    pub minutes: u8,
+
+    /// Seconds 0-59.
+    pub seconds: u8,
+
+    /// Frames 0-29, depending on the frame rate.
+    pub frames: u8,
+
+    /// Frame rate.
+    pub rate: FrameRate,
+}
+
+/// Quarter frame data byte split into its piece number and value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuarterFrame {
+    /// Piece number 0-7.
+    pub piece: u8,
+
+    /// 4-bit value of the piece.
+    pub value: u8,
+}
+
+impl QuarterFrame {
+    /// Split the data byte of a quarter frame message, the high bit is ignored.
+    pub const fn from_data(data: u8) -> Self {
+        Self {
+            piece: (data >> 4) & 0x07,
+            value: data & 0x0F,
+        }
+    }
+}
+
+/// Error variants for accumulating quarter frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MtcError {
+    /// Quarter frame piece didn't follow the previous one.
+    /// Only reported with strict ordering.
+    OutOfOrder,
+}
+
+/// Accumulator collecting the 8 pieces of quarter frame messages into a timecode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MtcAccumulator {
+    /// Values of the pieces received so far.
+    pieces: [u8; 8],
+
+    /// Piece number expected next.
+    next_piece: u8,
+
+    /// Report pieces arriving out of order as error.
+    strict_order: bool,
+}
+
+impl MtcAccumulator {
+    /// Returns a new accumulator. With `strict_order`, pieces not arriving in the
+    /// order 0 to 7 are reported as [`MtcError::OutOfOrder`].
+    pub fn new(strict_order: bool) -> Self {
+        Self {
+            pieces: [0; 8],
+            next_piece: 0,
+            strict_order,
+        }
+    }
+
+    /// Feed the data byte of a quarter frame message and return the timecode
+    /// once all 8 pieces have been received in order.
+    ///
+    /// A piece arriving out of order discards the pieces received so far,
+    /// accumulation restarts with the next piece 0.
+    pub fn feed(&mut self, data: u8) -> Result<Option<MtcTimecode>, MtcError> {
+        let QuarterFrame { piece, value } = QuarterFrame::from_data(data);
+
+        if piece != self.next_piece {
+            self.next_piece = 0;
+            if piece != 0 {
+                if self.strict_order {
+                    return Err(MtcError::OutOfOrder);
+                }
+                return Ok(None);
+            }
+        }
+
+        self.pieces[piece as usize] = value;
+
+        if piece < 7 {
+            self.next_piece = piece + 1;
+            return Ok(None);
+        }
+
+        self.next_piece = 0;
+        let pieces = &self.pieces;
+
+        Ok(Some(MtcTimecode {
+            hours: pieces[6] | (pieces[7] & 0x01) << 4,
+            minutes: pieces[4] | (pieces[5] & 0x03) << 4,
+            seconds: pieces[2] | (pieces[3] & 0x03) << 4,
+            frames: pieces[0] | (pieces[1] & 0x01) << 4,
+            rate: match (pieces[7] >> 1) & 0x03 {
+                0 => FrameRate::Fps24,
+                1 => FrameRate::Fps25,
+                2 => FrameRate::Fps30Drop,
+                _ => FrameRate::Fps30,
+            },
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,67 @@
+//! Unit tests
+
+use super::*;
+
+/// Quarter frames for 01:23:45:12 at 25 fps.
+const QUARTER_FRAMES: [u8; 8] = [0x0C, 0x10, 0x2D, 0x32, 0x47, 0x51, 0x61, 0x72];
+
+/// Quarter frames received in order.
+#[test]
+fn in_order() {
+    for strict_order in [false, true] {
+        let mut accumulator = MtcAccumulator::new(strict_order);
+
+        for data in &QUARTER_FRAMES[..7] {
+            assert_eq!(accumulator.feed(*data), Ok(None));
+        }
+
+        assert_eq!(
+            accumulator.feed(QUARTER_FRAMES[7]),
+            Ok(Some(MtcTimecode {
+                hours: 1,
+                minutes: 23,
+                seconds: 45,
+                frames: 12,
+                rate: FrameRate::Fps25,
+            }))
+        );
+    }
+}
+
+/// Shuffled quarter frames.
+#[test]
+fn out_of_order() {
+    let shuffled = [0, 1, 3, 2, 4, 5, 6, 7].map(|piece| QUARTER_FRAMES[piece]);
+
+    let mut accumulator = MtcAccumulator::new(false);
+    for data in shuffled {
+        assert_eq!(accumulator.feed(data), Ok(None));
+    }
+
+    let mut accumulator = MtcAccumulator::new(true);
+    assert_eq!(accumulator.feed(shuffled[0]), Ok(None));
+    assert_eq!(accumulator.feed(shuffled[1]), Ok(None));
+    assert_eq!(accumulator.feed(shuffled[2]), Err(MtcError::OutOfOrder));
+
+    // Accumulation restarts with piece 0.
+    for data in &QUARTER_FRAMES[..7] {
+        assert_eq!(accumulator.feed(*data), Ok(None));
+    }
+    assert!(accumulator.feed(QUARTER_FRAMES[7]).unwrap().is_some());
+}
+
+/// Data byte split into piece number and value.
+#[test]
+fn quarter_frame_split() {
+    assert_eq!(
+        QuarterFrame::from_data(0x73),
+        QuarterFrame { piece: 7, value: 3 }
+    );
+    assert_eq!(
+        QuarterFrame::from_data(0x0C),
+        QuarterFrame {
+            piece: 0,
+            value: 12
+        }
+    );
+}
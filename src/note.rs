@@ -0,0 +1,29 @@
+//! Note number conversions.
+
+/// Frequencies of all note numbers in Hz as Q16.16 fixed-point values, A4 (69) = 440 Hz.
+const FREQUENCIES_Q16: [u32; 128] = [
+    535809, 567670, 601425, 637188, 675077, 715219, 757749, 802807, 850544, 901120, 954703,
+    1011473, 1071618, 1135340, 1202851, 1274376, 1350154, 1430439, 1515497, 1605613, 1701088,
+    1802240, 1909407, 2022946, 2143237, 2270680, 2405702, 2548752, 2700309, 2860878, 3030994,
+    3211227, 3402176, 3604480, 3818814, 4045892, 4286473, 4541360, 4811404, 5097505, 5400618,
+    5721755, 6061989, 6422453, 6804352, 7208960, 7637627, 8091784, 8572947, 9082720, 9622807,
+    10195009, 10801236, 11443511, 12123977, 12844906, 13608704, 14417920, 15275254, 16183568,
+    17145893, 18165441, 19245614, 20390018, 21602472, 22887021, 24247954, 25689813, 27217409,
+    28835840, 30550508, 32367136, 34291786, 36330882, 38491228, 40780036, 43204943, 45774043,
+    48495909, 51379626, 54434817, 57671680, 61101017, 64734272, 68583572, 72661764, 76982457,
+    81560072, 86409886, 91548086, 96991818, 102759252, 108869635, 115343360, 122202033, 129468544,
+    137167144, 145323527, 153964914, 163120144, 172819773, 183096171, 193983636, 205518503,
+    217739269, 230686720, 244404066, 258937088, 274334289, 290647054, 307929828, 326240288,
+    345639545, 366192342, 387967272, 411037006, 435478539, 461373440, 488808132, 517874176,
+    548668578, 581294109, 615859655, 652480576, 691279090, 732384684, 775934544, 822074013,
+];
+
+/// Returns the frequency of a note number in Hz as Q16.16 fixed-point value,
+/// based on equal temperament with A4 (note 69) at 440 Hz.
+/// Table-driven, so usable on targets without FPU. Note numbers above 127 are clamped.
+pub fn note_to_frequency_q16(note: u8) -> u32 {
+    FREQUENCIES_Q16[note.min(127) as usize]
+}
+
+#[cfg(test)]
+mod tests;
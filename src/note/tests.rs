@@ -0,0 +1,16 @@
+//! Unit tests
+
+use super::*;
+
+/// Table compared against floating point calculation.
+#[test]
+fn frequency_table() {
+    for note in 0..=127u8 {
+        let expected = 440.0 * 2f64.powf((note as f64 - 69.0) / 12.0);
+        let frequency = note_to_frequency_q16(note) as f64 / 65536.0;
+        assert!((frequency - expected).abs() <= 1.0 / 65536.0);
+    }
+
+    assert_eq!(note_to_frequency_q16(69), 440 << 16);
+    assert_eq!(note_to_frequency_q16(200), note_to_frequency_q16(127));
+}
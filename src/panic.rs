@@ -0,0 +1,66 @@
+//! Detection of the "panic" gesture sent by controllers to silence all receivers.
+
+use crate::{ChannelModeMessage, MidiMessage};
+
+/// All sound off received.
+const ALL_SOUND_OFF: u8 = 0x01;
+
+/// Reset all controllers received.
+const RESET_ALL_CONTROLLERS: u8 = 0x02;
+
+/// All notes off received.
+const ALL_NOTES_OFF: u8 = 0x04;
+
+/// All messages forming the gesture received.
+const COMPLETE: u8 = ALL_SOUND_OFF | RESET_ALL_CONTROLLERS | ALL_NOTES_OFF;
+
+/// Panic gesture detected on a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Panic {
+    /// Channel in the range 0-15.
+    pub channel: u8,
+}
+
+/// Detector recognizing all notes off, all sound off and reset all controllers
+/// sent in direct succession on a channel, in any order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PanicDetector {
+    /// Channel mode messages of the gesture received per channel.
+    received: [u8; 16],
+}
+
+impl PanicDetector {
+    /// Returns a new detector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process a message and return the panic event once the gesture is complete
+    /// on its channel. Any other channel message on the channel restarts detection.
+    pub fn process(&mut self, message: &MidiMessage) -> Option<Panic> {
+        // Channels of hand-built messages above 15 wrap around.
+        let channel = message.channel()? & 0x0F;
+        let received = &mut self.received[channel as usize];
+
+        let flag = match message.channel_mode() {
+            Some((_, ChannelModeMessage::AllSoundOff)) => ALL_SOUND_OFF,
+            Some((_, ChannelModeMessage::ResetAllControllers)) => RESET_ALL_CONTROLLERS,
+            Some((_, ChannelModeMessage::AllNotesOff)) => ALL_NOTES_OFF,
+            _ => {
+                *received = 0;
+                return None;
+            }
+        };
+
+        *received |= flag;
+        if *received == COMPLETE {
+            *received = 0;
+            return Some(Panic { channel });
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,65 @@
+//! Unit tests
+
+use super::*;
+use crate::MidiStreamParser;
+
+/// Panic burst on a channel detected once.
+#[test]
+fn panic_burst() {
+    let mut parser = MidiStreamParser::<0>::new();
+    let mut detector = PanicDetector::new();
+    let mut events = Vec::new();
+
+    let bytes = [
+        0xB2, 123, 0, 0xB5, 120, 0, 0xB2, 120, 0, 121, 0, 0xF8, 0xB5, 7, 100,
+    ];
+
+    for byte in bytes {
+        if let Some(message) = parser.parse_typed(byte).unwrap() {
+            events.extend(detector.process(&message));
+        }
+    }
+
+    assert_eq!(events, [Panic { channel: 2 }]);
+}
+
+/// Other messages between the parts of the gesture.
+#[test]
+fn interrupted() {
+    let mut detector = PanicDetector::new();
+
+    let messages = [
+        (120, None),
+        (121, None),
+        (7, None),
+        (123, None),
+        (120, None),
+        (121, Some(Panic { channel: 0 })),
+    ];
+
+    for (controller, event) in messages {
+        let message = MidiMessage::ControlChange {
+            channel: 0,
+            controller,
+            value: 0,
+        };
+        assert_eq!(detector.process(&message), event);
+    }
+}
+
+/// Channel out of range of a hand-built message wrapped instead of panicking.
+#[test]
+fn channel_out_of_range() {
+    let mut detector = PanicDetector::new();
+
+    let events = [(123, None), (120, None), (121, Some(Panic { channel: 0 }))];
+
+    for (controller, event) in events {
+        let message = MidiMessage::ControlChange {
+            channel: 16,
+            controller,
+            value: 0,
+        };
+        assert_eq!(detector.process(&message), event);
+    }
+}
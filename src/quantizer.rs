@@ -0,0 +1,74 @@
+//! Transform snapping notes to a scale.
+
+use crate::MidiMessage;
+
+/// Transform snapping note numbers of note on and note off messages to the nearest
+/// note of a scale. If two scale notes are equally near, the lower one is chosen.
+///
+/// Snapping only depends on the note number and the scale, which is fixed
+/// for the lifetime of the quantizer, so a note off always lands on the same
+/// note as its note on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quantizer {
+    /// Pitch classes of the scale, starting with C.
+    scale: [bool; 12],
+}
+
+impl Quantizer {
+    /// Pitch classes of the C major scale.
+    pub const C_MAJOR: [bool; 12] = [
+        true, false, true, false, true, true, false, true, false, true, false, true,
+    ];
+
+    /// Returns a new quantizer for a scale given as mask of pitch classes, starting with C.
+    /// An empty scale passes all notes unchanged.
+    pub fn new(scale: [bool; 12]) -> Self {
+        Self { scale }
+    }
+
+    /// Returns the note of the scale nearest to `note`.
+    pub fn quantize_note(&self, note: u8) -> u8 {
+        for distance in 0..12 {
+            if let Some(lower) = note.checked_sub(distance) {
+                if self.scale[lower as usize % 12] {
+                    return lower;
+                }
+            }
+            let upper = note.saturating_add(distance);
+            if upper <= 127 && self.scale[upper as usize % 12] {
+                return upper;
+            }
+        }
+
+        note
+    }
+
+    /// Returns the message with the note number quantized for note on and note off
+    /// messages, other messages are returned unchanged.
+    pub fn process<'a>(&self, message: MidiMessage<'a>) -> MidiMessage<'a> {
+        match message {
+            MidiMessage::NoteOn {
+                channel,
+                note,
+                velocity,
+            } => MidiMessage::NoteOn {
+                channel,
+                note: self.quantize_note(note),
+                velocity,
+            },
+            MidiMessage::NoteOff {
+                channel,
+                note,
+                velocity,
+            } => MidiMessage::NoteOff {
+                channel,
+                note: self.quantize_note(note),
+                velocity,
+            },
+            _ => message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
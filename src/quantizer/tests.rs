@@ -0,0 +1,56 @@
+//! Unit tests
+
+use super::*;
+
+/// Chromatic run snapped to C major.
+#[test]
+fn chromatic_to_c_major() {
+    let quantizer = Quantizer::new(Quantizer::C_MAJOR);
+
+    let notes: Vec<u8> = (60..72).map(|note| quantizer.quantize_note(note)).collect();
+
+    assert_eq!(notes, [60, 60, 62, 62, 64, 65, 65, 67, 67, 69, 69, 71]);
+}
+
+/// Note off lands on the same note as its note on.
+#[test]
+fn note_on_off_match() {
+    let quantizer = Quantizer::new(Quantizer::C_MAJOR);
+
+    for note in 0..=127 {
+        let note_on = quantizer.process(MidiMessage::NoteOn {
+            channel: 0,
+            note,
+            velocity: 100,
+        });
+        let note_off = quantizer.process(MidiMessage::NoteOff {
+            channel: 0,
+            note,
+            velocity: 0,
+        });
+
+        match (note_on, note_off) {
+            (MidiMessage::NoteOn { note: on, .. }, MidiMessage::NoteOff { note: off, .. }) => {
+                assert_eq!(on, off);
+                assert!(on <= 127);
+                assert!(Quantizer::C_MAJOR[on as usize % 12]);
+            }
+            _ => panic!("unexpected message type"),
+        }
+    }
+}
+
+/// Empty scale and other messages pass unchanged.
+#[test]
+fn passthrough() {
+    let quantizer = Quantizer::new([false; 12]);
+    assert_eq!(quantizer.quantize_note(61), 61);
+
+    let quantizer = Quantizer::new(Quantizer::C_MAJOR);
+    let message = MidiMessage::ControlChange {
+        channel: 0,
+        controller: 61,
+        value: 61,
+    };
+    assert_eq!(quantizer.process(message), message);
+}
@@ -0,0 +1,145 @@
+//! Queue decoupling the reception of bytes from processing the messages.
+
+use crate::{MidiStreamParser, ParserConfig, ParserError};
+
+/// Completed message stored in a [`MidiQueue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueuedMessage<const ENTRY_LEN: usize> {
+    /// Message bytes.
+    bytes: [u8; ENTRY_LEN],
+
+    /// Number of used bytes.
+    length: usize,
+
+    /// SysEx message was closed at the end of the entry.
+    truncated: bool,
+}
+
+impl<const ENTRY_LEN: usize> QueuedMessage<ENTRY_LEN> {
+    /// Returns the message bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.length]
+    }
+
+    /// Returns `true` if the message is a SysEx message that was longer than `ENTRY_LEN`
+    /// bytes and has been closed with `0xF7` at the end of the entry.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+/// Queue holding up to `CAP` completed messages of up to `ENTRY_LEN` bytes each.
+///
+/// Bytes are fed from the receiving side, e.g. a UART interrupt handler, and the messages
+/// are drained by the main loop. Longer SysEx messages are truncated to `ENTRY_LEN` bytes.
+/// When the queue is full, new messages are dropped and counted.
+/// Access from both sides must be synchronized, e.g. by a critical section.
+#[derive(Debug)]
+pub struct MidiQueue<const CAP: usize, const ENTRY_LEN: usize> {
+    /// Parser constructing the messages.
+    parser: MidiStreamParser<ENTRY_LEN>,
+
+    /// Storage for the queued messages.
+    entries: [QueuedMessage<ENTRY_LEN>; CAP],
+
+    /// Index of the oldest message.
+    head: usize,
+
+    /// Number of queued messages.
+    length: usize,
+
+    /// Number of messages dropped because the queue was full.
+    dropped: usize,
+}
+
+impl<const CAP: usize, const ENTRY_LEN: usize> Default for MidiQueue<CAP, ENTRY_LEN> {
+    /// Returns a new queue with default values.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CAP: usize, const ENTRY_LEN: usize> MidiQueue<CAP, ENTRY_LEN> {
+    /// Returns a new empty queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ENTRY_LEN` is below 3, which is required for channel messages.
+    pub fn new() -> Self {
+        assert!(ENTRY_LEN >= 3, "entry length must be at least 3");
+
+        Self {
+            parser: MidiStreamParser::with_config(ParserConfig {
+                truncate_sysex_on_overflow: true,
+                ..Default::default()
+            }),
+            entries: [QueuedMessage {
+                bytes: [0; ENTRY_LEN],
+                length: 0,
+                truncated: false,
+            }; CAP],
+            head: 0,
+            length: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Feed a byte into the parser and enqueue a completed message.
+    /// Parser errors are returned, the message is dropped if the queue is full.
+    pub fn push_byte(&mut self, byte: u8) -> Result<(), ParserError> {
+        let mut entry = match self.parser.parse(byte)? {
+            Some(message) => {
+                let mut bytes = [0; ENTRY_LEN];
+                bytes[..message.len()].copy_from_slice(message);
+                QueuedMessage {
+                    bytes,
+                    length: message.len(),
+                    truncated: false,
+                }
+            }
+            None => return Ok(()),
+        };
+        entry.truncated =
+            entry.bytes[0] == 0xF0 && entry.length > 1 && self.parser.last_sysex_truncated();
+
+        if self.length >= CAP {
+            self.dropped += 1;
+        } else {
+            self.entries[(self.head + self.length) % CAP] = entry;
+            self.length += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Remove and return the oldest message.
+    pub fn pop(&mut self) -> Option<QueuedMessage<ENTRY_LEN>> {
+        if self.length == 0 {
+            return None;
+        }
+
+        let entry = self.entries[self.head];
+        self.head = (self.head + 1) % CAP;
+        self.length -= 1;
+
+        Some(entry)
+    }
+
+    /// Returns the number of queued messages.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if no messages are queued.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns the number of messages dropped because the queue was full.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,70 @@
+//! Unit tests
+
+use super::*;
+
+/// Mixed messages enqueued and drained in order.
+#[test]
+fn enqueue_drain() {
+    let mut queue = MidiQueue::<4, 6>::new();
+
+    let bytes = [0x90, 60, 100, 0xF8, 61, 0, 0xF0, 0x41, 0x10, 0xF7];
+    for byte in bytes {
+        queue.push_byte(byte).unwrap();
+    }
+    assert_eq!(queue.len(), 4);
+
+    let messages: [&[u8]; 4] = [
+        &[0x90, 60, 100],
+        &[0xF8],
+        &[0x90, 61, 0],
+        &[0xF0, 0x41, 0x10, 0xF7],
+    ];
+    for message in messages {
+        let entry = queue.pop().unwrap();
+        assert_eq!(entry.as_bytes(), message);
+        assert!(!entry.truncated());
+    }
+    assert!(queue.is_empty());
+    assert_eq!(queue.pop(), None);
+
+    // Ring storage wrapping around.
+    for byte in [0xB0, 7, 90] {
+        queue.push_byte(byte).unwrap();
+    }
+    assert_eq!(queue.pop().unwrap().as_bytes(), [0xB0, 7, 90]);
+}
+
+/// SysEx message longer than an entry.
+#[test]
+fn truncated_sysex() {
+    let mut queue = MidiQueue::<4, 6>::new();
+
+    for byte in [0xF0, 1, 2, 3, 4, 5, 6, 7, 0xF7, 0xC0, 5] {
+        queue.push_byte(byte).unwrap();
+    }
+
+    let entry = queue.pop().unwrap();
+    assert_eq!(entry.as_bytes(), [0xF0, 1, 2, 3, 4, 0xF7]);
+    assert!(entry.truncated());
+
+    let entry = queue.pop().unwrap();
+    assert_eq!(entry.as_bytes(), [0xC0, 5]);
+    assert!(!entry.truncated());
+}
+
+/// Messages dropped when the queue is full.
+#[test]
+fn full_queue() {
+    let mut queue = MidiQueue::<2, 3>::new();
+
+    for _ in 0..5 {
+        queue.push_byte(0xF8).unwrap();
+    }
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.dropped(), 3);
+
+    queue.pop().unwrap();
+    queue.push_byte(0xFA).unwrap();
+    assert_eq!(queue.pop().unwrap().as_bytes(), [0xF8]);
+    assert_eq!(queue.pop().unwrap().as_bytes(), [0xFA]);
+}
@@ -0,0 +1,67 @@
+//! Parsing from readers of the standard library, e.g. files with MIDI captures.
+
+use std::io::{self, ErrorKind, Read};
+
+use crate::{MidiMessage, MidiStreamParser, ParserError, SysExSink};
+
+/// Size of the buffer the reader is read into.
+const READ_BUFFER_LEN: usize = 256;
+
+/// Position at which the stream returned by the reader ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamEnd {
+    /// Ended on a message boundary.
+    Clean,
+
+    /// Ended in the middle of a message. A SysEx message in progress has been flushed.
+    MidMessage,
+}
+
+/// Parse all bytes read from `reader` until the end of the stream, passing the completed
+/// messages and parser errors to `on_message`. SysEx payload bytes are streamed to `sink`,
+/// see [`MidiStreamParser::parse_to_sink`].
+///
+/// At the end of the stream, a SysEx message in progress is flushed by calling
+/// [`SysExSink::finish`] and aborted in the parser. The returned [`StreamEnd`] tells if
+/// the stream ended on a message boundary. Errors of the reader are returned immediately.
+pub fn parse_from_reader_buffered<const SYSEX_MAX_LEN: usize>(
+    mut reader: impl Read,
+    parser: &mut MidiStreamParser<SYSEX_MAX_LEN>,
+    sink: &mut impl SysExSink,
+    mut on_message: impl FnMut(Result<MidiMessage<'_>, ParserError>),
+) -> io::Result<StreamEnd> {
+    let mut buffer = [0; READ_BUFFER_LEN];
+
+    loop {
+        let length = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(length) => length,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+
+        for byte in &buffer[..length] {
+            match parser.parse_to_sink(*byte, sink) {
+                Ok(Some(message)) => on_message(Ok(MidiMessage::decode(message))),
+                Ok(None) => {}
+                Err(error) => on_message(Err(error)),
+            }
+        }
+    }
+
+    if !parser.mid_message() {
+        return Ok(StreamEnd::Clean);
+    }
+
+    if parser.sysex_running {
+        if let Err(error) = sink.finish() {
+            on_message(Err(ParserError::Sink(error)));
+        }
+        parser.clear_sysex();
+    }
+
+    Ok(StreamEnd::MidMessage)
+}
+
+#[cfg(test)]
+mod tests;
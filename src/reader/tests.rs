@@ -0,0 +1,85 @@
+//! Unit tests
+
+use super::*;
+use crate::SinkError;
+use std::io::Cursor;
+
+/// Sink recording the payload bytes and the number of finished messages.
+#[derive(Default)]
+struct RecordingSink {
+    payload: Vec<u8>,
+    finished: usize,
+}
+
+impl SysExSink for RecordingSink {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), SinkError> {
+        self.payload.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), SinkError> {
+        self.finished += 1;
+        Ok(())
+    }
+}
+
+/// Stream ending on a message boundary.
+#[test]
+fn clean_end() {
+    let mut parser = MidiStreamParser::<0>::new();
+    let mut sink = RecordingSink::default();
+    let mut messages = Vec::new();
+
+    let reader = Cursor::new([0x90, 60, 100, 0xF0, 0x41, 0xF7, 0x10, 0xC0, 5]);
+    let end = parse_from_reader_buffered(reader, &mut parser, &mut sink, |message| {
+        messages.push(message.map(|message| message.to_static().unwrap()));
+    });
+
+    assert_eq!(end.unwrap(), StreamEnd::Clean);
+    assert_eq!(
+        messages,
+        [
+            Ok(MidiMessage::NoteOn {
+                channel: 0,
+                note: 60,
+                velocity: 100
+            }),
+            Err(ParserError::InvalidStatus),
+            Ok(MidiMessage::ProgramChange {
+                channel: 0,
+                program: 5
+            }),
+        ]
+    );
+    assert_eq!(sink.payload, [0x41]);
+    assert_eq!(sink.finished, 1);
+}
+
+/// Stream ending in the middle of a SysEx message.
+#[test]
+fn mid_sysex_end() {
+    let mut parser = MidiStreamParser::<0>::new();
+    let mut sink = RecordingSink::default();
+    let mut messages = 0;
+
+    let reader = Cursor::new([0xF8, 0xF0, 0x41, 0x10, 0x42]);
+    let end = parse_from_reader_buffered(reader, &mut parser, &mut sink, |_| messages += 1);
+
+    assert_eq!(end.unwrap(), StreamEnd::MidMessage);
+    assert_eq!(messages, 1);
+    assert_eq!(sink.payload, [0x41, 0x10, 0x42]);
+    assert_eq!(sink.finished, 1);
+    assert!(!parser.mid_message());
+}
+
+/// Stream ending in the middle of a channel message.
+#[test]
+fn mid_channel_message_end() {
+    let mut parser = MidiStreamParser::<0>::new();
+
+    let reader = Cursor::new([0x90, 60]);
+    let end =
+        parse_from_reader_buffered(reader, &mut parser, &mut RecordingSink::default(), |_| {});
+
+    assert_eq!(end.unwrap(), StreamEnd::MidMessage);
+}
@@ -0,0 +1,106 @@
+//! Round-trip check of parser and encoder for tests, enabled by the `test-util` feature.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{decode_status, ByteClass, CollectMidi, MidiStreamEncoder, StatusInfo};
+
+/// Returns `true` if parsing `bytes` into messages and encoding them again results in
+/// the normalized input.
+///
+/// Normalizing expands running status and moves system realtime messages in front of
+/// the message they occur in, as the parser returns them first. Returns `false` for
+/// streams that are not valid, i.e. containing data bytes without status byte, undefined
+/// system common status bytes, incomplete messages or an end of SysEx without start.
+pub fn roundtrip_eq(bytes: &[u8]) -> bool {
+    let normalized = match normalize(bytes) {
+        Some(normalized) => normalized,
+        None => return false,
+    };
+
+    let encoder = MidiStreamEncoder::new();
+    let mut encoded = Vec::with_capacity(normalized.len());
+
+    for message in bytes.iter().copied().collect_midi().iter() {
+        let mut buf = vec![0; message.as_sysex().map_or(3, |sysex| sysex.as_bytes().len())];
+        match encoder.encode(&message, &mut buf) {
+            Ok(length) => encoded.extend_from_slice(&buf[..length]),
+            Err(_) => return false,
+        }
+    }
+
+    encoded == normalized
+}
+
+/// Returns the normalized form of a valid stream, see [`roundtrip_eq`].
+fn normalize(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut normalized = Vec::with_capacity(bytes.len());
+    let mut message = Vec::new();
+    let mut running_status = None;
+    let mut expected_length = 0;
+    let mut sysex = false;
+
+    for &byte in bytes {
+        match byte {
+            0xF8..=0xFF => normalized.push(byte),
+            0x00..=0x7F if sysex => message.push(byte),
+            0x00..=0x7F => {
+                if message.is_empty() {
+                    message.push(running_status?);
+                }
+                message.push(byte);
+                if message.len() == expected_length {
+                    normalized.append(&mut message);
+                }
+            }
+            0xF7 => {
+                if !sysex {
+                    return None;
+                }
+                message.push(byte);
+                normalized.append(&mut message);
+                sysex = false;
+            }
+            _ => {
+                if !message.is_empty() {
+                    // Incomplete message or SysEx interrupted by a status byte.
+                    return None;
+                }
+                running_status = None;
+                match decode_status(byte) {
+                    StatusInfo {
+                        class: ByteClass::SysExStart,
+                        ..
+                    } => {
+                        message.push(byte);
+                        sysex = true;
+                    }
+                    StatusInfo {
+                        length: Some(1), ..
+                    } => normalized.push(byte),
+                    StatusInfo {
+                        class,
+                        length: Some(length),
+                    } => {
+                        expected_length = length;
+                        message.push(byte);
+                        if class == ByteClass::ChannelVoiceStatus {
+                            running_status = Some(byte);
+                        }
+                    }
+                    // Undefined system common status byte.
+                    StatusInfo { length: None, .. } => return None,
+                }
+            }
+        }
+    }
+
+    if message.is_empty() {
+        Some(normalized)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests;
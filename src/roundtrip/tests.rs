@@ -0,0 +1,96 @@
+//! Unit tests
+
+use super::*;
+use crate::expected_message_length;
+use crate::tests::Random;
+
+/// Returns a random valid stream using running status and interleaved realtime messages.
+fn random_stream(random: &mut Random) -> Vec<u8> {
+    let mut stream = Vec::new();
+    let mut running_status = None;
+
+    for _ in 0..random.below(20) {
+        let body: Vec<u8> = match random.below(6) {
+            0 => {
+                // Channel message, reusing the running status if possible.
+                let status = match running_status {
+                    Some(status) if random.below(2) == 0 => status,
+                    _ => 0x80 | random.below(0x70),
+                };
+                let length = expected_message_length(status).unwrap();
+                let data: Vec<u8> = (1..length).map(|_| random.below(0x80)).collect();
+                let with_status = Some(status) != running_status;
+                running_status = Some(status);
+                if with_status {
+                    [vec![status], data].concat()
+                } else {
+                    data
+                }
+            }
+            1 => {
+                running_status = None;
+                let payload: Vec<u8> = (0..random.below(10)).map(|_| random.below(0x80)).collect();
+                [vec![0xF0], payload, vec![0xF7]].concat()
+            }
+            2 => {
+                running_status = None;
+                match random.below(4) {
+                    0 => vec![0xF1, random.below(0x80)],
+                    1 => vec![0xF2, random.below(0x80), random.below(0x80)],
+                    2 => vec![0xF3, random.below(0x80)],
+                    _ => vec![0xF6],
+                }
+            }
+            _ => vec![
+                0x90 | random.below(16),
+                random.below(0x80),
+                random.below(0x80),
+            ],
+        };
+
+        // Channel messages sent with status byte start a new running status.
+        if body[0] >= 0x80 && body[0] < 0xF0 {
+            running_status = Some(body[0]);
+        }
+
+        for byte in body {
+            if random.below(8) == 0 {
+                stream.push([0xF8, 0xFA, 0xFB, 0xFC, 0xFE, 0xFF][random.below(6) as usize]);
+            }
+            stream.push(byte);
+        }
+    }
+
+    stream
+}
+
+/// Invariant holding for random valid streams.
+#[test]
+fn random_streams() {
+    let mut random = Random(0x1234_5678);
+
+    for _ in 0..1000 {
+        let stream = random_stream(&mut random);
+        assert!(roundtrip_eq(&stream), "stream: {:02X?}", stream);
+    }
+}
+
+/// Fixed streams with running status and realtime inside messages.
+#[test]
+fn fixed_streams() {
+    assert!(roundtrip_eq(&[]));
+    assert!(roundtrip_eq(&[0x90, 60, 100, 61, 0xF8, 90]));
+    assert!(roundtrip_eq(&[0xF0, 0x41, 0xFE, 0x10, 0xF7, 0xC0, 5, 6]));
+    assert!(roundtrip_eq(&[0xF2, 1, 2, 0xF6, 0xF3, 5]));
+}
+
+/// Streams that are not valid.
+#[test]
+fn invalid_streams() {
+    assert!(!roundtrip_eq(&[60, 100]));
+    assert!(!roundtrip_eq(&[0x90, 60]));
+    assert!(!roundtrip_eq(&[0xF2, 1, 2, 3]));
+    assert!(!roundtrip_eq(&[0xF7]));
+    assert!(!roundtrip_eq(&[0xF0, 0x41, 0x90, 60, 100]));
+    assert!(!roundtrip_eq(&[0xF4]));
+}
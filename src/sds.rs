@@ -0,0 +1,103 @@
+//! Decoder for the Sample Dump Standard (SDS).
+
+/// Sample dump header, sent as universal non-realtime SysEx message with sub-ID `0x01`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SdsHeader {
+    /// Device ID of the sender.
+    pub device_id: u8,
+
+    /// Sample number, 14-bit value.
+    pub sample_number: u16,
+
+    /// Significant bits per sample word, 8-28.
+    pub format_bits: u8,
+
+    /// Sample period in nanoseconds, 21-bit value.
+    pub period: u32,
+
+    /// Sample length in words, 21-bit value.
+    pub length: u32,
+
+    /// Sustain loop start and end as word numbers, 21-bit values.
+    pub loop_points: (u32, u32),
+
+    /// Loop type, 0 for forward only, 1 for alternating and `0x7F` for loop off.
+    pub loop_type: u8,
+}
+
+impl SdsHeader {
+    /// Length of the header message including start and end of SysEx.
+    pub const MESSAGE_LEN: usize = 21;
+
+    /// Decode a complete dump header message starting with `0xF0` and ending with `0xF7`.
+    ///
+    /// Returns `None` for other messages, messages of wrong length, data bytes with the
+    /// high bit set and sample formats outside of 8-28 bits.
+    pub fn decode(msg: &[u8]) -> Option<Self> {
+        let data = match *msg {
+            [0xF0, 0x7E, ref data @ .., 0xF7] if msg.len() == Self::MESSAGE_LEN => data,
+            _ => return None,
+        };
+
+        if data.iter().any(|byte| *byte > 0x7F) || data[1] != 0x01 {
+            return None;
+        }
+
+        let format_bits = data[4];
+        if !(8..=28).contains(&format_bits) {
+            return None;
+        }
+
+        Some(Self {
+            device_id: data[0],
+            sample_number: data[2] as u16 | (data[3] as u16) << 7,
+            format_bits,
+            period: word_21(&data[5..8]),
+            length: word_21(&data[8..11]),
+            loop_points: (word_21(&data[11..14]), word_21(&data[14..17])),
+            loop_type: data[17],
+        })
+    }
+}
+
+/// Sample dump request, sent as universal non-realtime SysEx message with sub-ID `0x03`
+/// to ask a device for the header and data of a sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SdsRequest {
+    /// Device ID of the receiver.
+    pub device_id: u8,
+
+    /// Requested sample number, 14-bit value.
+    pub sample_number: u16,
+}
+
+impl SdsRequest {
+    /// Length of the request message including start and end of SysEx.
+    pub const MESSAGE_LEN: usize = 7;
+
+    /// Decode a complete dump request message starting with `0xF0` and ending with `0xF7`.
+    ///
+    /// Returns `None` for other messages, messages of wrong length and data bytes with the
+    /// high bit set.
+    pub fn decode(msg: &[u8]) -> Option<Self> {
+        match *msg {
+            [0xF0, 0x7E, device_id, 0x03, low, high, 0xF7]
+                if device_id < 0x80 && low < 0x80 && high < 0x80 =>
+            {
+                Some(Self {
+                    device_id,
+                    sample_number: low as u16 | (high as u16) << 7,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the 21-bit value of 3 data bytes, least significant first.
+fn word_21(bytes: &[u8]) -> u32 {
+    bytes[0] as u32 | (bytes[1] as u32) << 7 | (bytes[2] as u32) << 14
+}
+
+#[cfg(test)]
+mod tests;
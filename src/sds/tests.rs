@@ -0,0 +1,72 @@
+//! Unit tests
+
+use super::*;
+
+/// Header of sample 5 with 16 bits at 44.1 kHz, 100000 words and a forward loop.
+const HEADER: [u8; 21] = [
+    0xF0, 0x7E, 0x00, 0x01, 0x05, 0x00, 0x10, 0x13, 0x31, 0x01, 0x20, 0x0D, 0x06, 0x00, 0x00, 0x00,
+    0x1F, 0x0D, 0x06, 0x00, 0xF7,
+];
+
+/// Decoding of a known header.
+#[test]
+fn decode_header() {
+    assert_eq!(
+        SdsHeader::decode(&HEADER),
+        Some(SdsHeader {
+            device_id: 0,
+            sample_number: 5,
+            format_bits: 16,
+            period: 22_675,
+            length: 100_000,
+            loop_points: (0, 99_999),
+            loop_type: 0,
+        })
+    );
+}
+
+/// Messages other than a valid header.
+#[test]
+fn invalid_header() {
+    assert_eq!(SdsHeader::decode(&HEADER[..20]), None);
+
+    let mut data_packet = HEADER;
+    data_packet[3] = 0x02;
+    assert_eq!(SdsHeader::decode(&data_packet), None);
+
+    let mut realtime = HEADER;
+    realtime[1] = 0x7F;
+    assert_eq!(SdsHeader::decode(&realtime), None);
+
+    let mut format = HEADER;
+    format[6] = 30;
+    assert_eq!(SdsHeader::decode(&format), None);
+
+    let mut data = HEADER;
+    data[10] = 0x80;
+    assert_eq!(SdsHeader::decode(&data), None);
+}
+
+/// Decoding of a request for sample 300 and messages other than a valid request.
+#[test]
+fn decode_request() {
+    let request = [0xF0, 0x7E, 0x01, 0x03, 0x2C, 0x02, 0xF7];
+    assert_eq!(
+        SdsRequest::decode(&request),
+        Some(SdsRequest {
+            device_id: 1,
+            sample_number: 300,
+        })
+    );
+
+    let mut header = request;
+    header[3] = 0x01;
+    assert_eq!(SdsRequest::decode(&header), None);
+
+    let mut data = request;
+    data[5] = 0x80;
+    assert_eq!(SdsRequest::decode(&data), None);
+
+    assert_eq!(SdsRequest::decode(&request[..6]), None);
+    assert_eq!(SdsRequest::decode(&HEADER), None);
+}
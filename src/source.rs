@@ -0,0 +1,93 @@
+//! Parsing from fallible byte sources such as serial ports.
+
+use crate::{MidiMessage, MidiStreamParser, ParserError};
+
+/// Error of a [`FallibleParser`], keeping errors of the source apart from parser errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceOrParseError<E> {
+    /// Reading from the source failed.
+    Source(E),
+
+    /// Parsing the bytes failed.
+    Parse(ParserError),
+}
+
+/// Adapter parsing messages from an iterator over `Result<u8, E>`, e.g. the bytes
+/// read from a serial port.
+#[derive(Debug)]
+pub struct FallibleParser<I, const SYSEX_MAX_LEN: usize> {
+    /// Source of bytes.
+    source: I,
+
+    /// Parser constructing the messages.
+    parser: MidiStreamParser<SYSEX_MAX_LEN>,
+
+    /// Source returned an error, no more bytes are read.
+    failed: bool,
+}
+
+impl<I, E, const SYSEX_MAX_LEN: usize> FallibleParser<I, SYSEX_MAX_LEN>
+where
+    I: Iterator<Item = Result<u8, E>>,
+{
+    /// Returns a new adapter reading from `source` and parsing with `parser`.
+    pub fn new(source: I, parser: MidiStreamParser<SYSEX_MAX_LEN>) -> Self {
+        Self {
+            source,
+            parser,
+            failed: false,
+        }
+    }
+
+    /// Returns the parser.
+    pub fn parser(&self) -> &MidiStreamParser<SYSEX_MAX_LEN> {
+        &self.parser
+    }
+
+    /// Read bytes until the next message is completed and return it.
+    ///
+    /// Parser errors are returned and parsing continues with the next call.
+    /// An error of the source is returned once, afterwards and at the end of the
+    /// source `None` is returned. The message borrows the adapter, so it must be
+    /// processed before the next call.
+    pub fn next_message(&mut self) -> Option<Result<MidiMessage<'_>, SourceOrParseError<E>>> {
+        if self.failed {
+            return None;
+        }
+
+        loop {
+            let byte = match self.source.next()? {
+                Ok(byte) => byte,
+                Err(error) => {
+                    self.failed = true;
+                    return Some(Err(SourceOrParseError::Source(error)));
+                }
+            };
+
+            match self.parser.parse(byte) {
+                Ok(Some(_)) => {}
+                Ok(None) => continue,
+                Err(error) => return Some(Err(SourceOrParseError::Parse(error))),
+            }
+
+            let filter = self.parser.message_filter;
+            let accepted = self
+                .parser
+                .last_message()
+                .map(MidiMessage::decode)
+                .map_or(false, |message| {
+                    filter.map_or(true, |filter| filter.accepts(&message))
+                });
+            if accepted {
+                break;
+            }
+        }
+
+        self.parser
+            .last_message()
+            .map(|bytes| Ok(MidiMessage::decode(bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,49 @@
+//! Unit tests
+
+use super::*;
+
+/// Source failing after a complete and a partial message.
+#[test]
+fn source_error() {
+    let bytes: [Result<u8, &str>; 6] = [Ok(0x90), Ok(60), Ok(100), Ok(62), Err("timeout"), Ok(0)];
+    let mut parser = FallibleParser::new(bytes.iter().copied(), MidiStreamParser::<16>::new());
+
+    assert_eq!(
+        parser.next_message(),
+        Some(Ok(MidiMessage::NoteOn {
+            channel: 0,
+            note: 60,
+            velocity: 100
+        }))
+    );
+    assert_eq!(
+        parser.next_message(),
+        Some(Err(SourceOrParseError::Source("timeout")))
+    );
+    assert_eq!(parser.next_message(), None);
+    assert_eq!(parser.parser().pending_len(), 2);
+}
+
+/// Parser errors returned without stopping.
+#[test]
+fn parse_error() {
+    let bytes: [Result<u8, ()>; 4] = [Ok(60), Ok(0xC0), Ok(5), Ok(0xF8)];
+    let mut parser = FallibleParser::new(bytes.iter().copied(), MidiStreamParser::<16>::new());
+
+    assert_eq!(
+        parser.next_message(),
+        Some(Err(SourceOrParseError::Parse(ParserError::InvalidStatus)))
+    );
+    assert_eq!(
+        parser.next_message(),
+        Some(Ok(MidiMessage::ProgramChange {
+            channel: 0,
+            program: 5
+        }))
+    );
+    assert!(matches!(
+        parser.next_message(),
+        Some(Ok(MidiMessage::SystemRealtime(_)))
+    ));
+    assert_eq!(parser.next_message(), None);
+}
@@ -0,0 +1,162 @@
+//! Byte ranges of the messages in a buffer.
+
+use core::ops::Range;
+
+use crate::{MidiStreamParser, ParserConfig, ParserError, SinkError, SysExSink};
+
+/// Returns an iterator over the byte ranges of all complete messages in `bytes`.
+///
+/// Each range spans from the status byte, or the first data byte of a message using
+/// running status, up to and including the last byte of the message. SysEx messages
+/// are not limited in length. A system realtime message occurring inside another message
+/// gets its own range within the range of the enclosing message.
+///
+/// Bytes that can't be assigned to a message are reported as error, iteration continues
+/// afterwards. Incomplete messages at the end of the buffer are not reported. Messages
+/// are parsed following the specification, see [`ParserConfig::spec_compliant`].
+pub fn message_spans(bytes: &[u8]) -> MessageSpans<'_> {
+    MessageSpans {
+        bytes,
+        position: 0,
+        start: None,
+        message: None,
+        parser: MidiStreamParser::with_config(ParserConfig {
+            spec_compliant: true,
+            ..ParserConfig::new()
+        }),
+    }
+}
+
+/// Iterator returned by [`message_spans`].
+#[derive(Debug)]
+pub struct MessageSpans<'a> {
+    /// Buffer to iterate over.
+    bytes: &'a [u8],
+
+    /// Index of the next byte.
+    position: usize,
+
+    /// Index of the first byte of the message in progress.
+    start: Option<usize>,
+
+    /// Bytes and length of the last returned message if it is not a SysEx message.
+    message: Option<([u8; 3], usize)>,
+
+    /// Parser tracking the message boundaries, SysEx bytes are not buffered.
+    parser: MidiStreamParser<0>,
+}
+
+impl<'a> MessageSpans<'a> {
+    /// Returns an iterator over the messages instead of their byte ranges.
+    ///
+    /// Unlike the ranges, messages other than SysEx are expanded to include their status
+    /// byte if they use running status and exclude system realtime messages occurring
+    /// inside of them. SysEx messages are borrowed from the buffer as they are, so system
+    /// realtime messages inside of them are returned separately before and kept in the
+    /// SysEx bytes.
+    pub fn expanded(self) -> ExpandedMessages<'a> {
+        ExpandedMessages { spans: self }
+    }
+}
+
+impl<'a> Iterator for MessageSpans<'a> {
+    type Item = Result<Range<usize>, ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(&byte) = self.bytes.get(self.position) {
+            let index = self.position;
+            self.position += 1;
+
+            let sysex_end = byte == 0xF7 && self.parser.sysex_running;
+
+            match byte {
+                0x00..=0x7F if self.start.is_none() => self.start = Some(index),
+                0x80..=0xF6 => self.start = Some(index),
+                _ => {}
+            }
+
+            match self.parser.parse_to_sink(byte, &mut DiscardSink) {
+                Ok(Some(message)) => {
+                    let mut bytes = [0; 3];
+                    bytes[..message.len()].copy_from_slice(message);
+                    self.message = Some((bytes, message.len()));
+
+                    if byte >= 0xF8 {
+                        return Some(Ok(index..index + 1));
+                    }
+
+                    let start = self.start.take().unwrap_or(index);
+                    return Some(Ok(start..index + 1));
+                }
+                Ok(None) if sysex_end => {
+                    self.message = None;
+                    let start = self.start.take().unwrap_or(index);
+                    return Some(Ok(start..index + 1));
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    self.start = None;
+                    return Some(Err(error));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Iterator returned by [`MessageSpans::expanded`].
+#[derive(Debug)]
+pub struct ExpandedMessages<'a> {
+    /// Iterator over the byte ranges.
+    spans: MessageSpans<'a>,
+}
+
+impl<'a> Iterator for ExpandedMessages<'a> {
+    type Item = Result<ExpandedMessage<'a>, ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = match self.spans.next()? {
+            Ok(range) => range,
+            Err(error) => return Some(Err(error)),
+        };
+
+        Some(Ok(match self.spans.message {
+            Some((bytes, length)) => ExpandedMessage::Short(bytes, length),
+            None => ExpandedMessage::SysEx(&self.spans.bytes[range]),
+        }))
+    }
+}
+
+/// Message returned by [`ExpandedMessages`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpandedMessage<'a> {
+    /// Message other than SysEx with its bytes and length, including the status byte.
+    Short([u8; 3], usize),
+
+    /// SysEx message borrowed from the buffer, including the start and end bytes
+    /// and any system realtime bytes in between.
+    SysEx(&'a [u8]),
+}
+
+impl<'a> ExpandedMessage<'a> {
+    /// Returns the bytes of the message.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Short(bytes, length) => &bytes[..*length],
+            Self::SysEx(bytes) => bytes,
+        }
+    }
+}
+
+/// Sink dropping all SysEx bytes.
+struct DiscardSink;
+
+impl SysExSink for DiscardSink {
+    fn write(&mut self, _bytes: &[u8]) -> Result<(), SinkError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,81 @@
+//! Unit tests
+
+use super::*;
+
+/// Spans of channel, system and SysEx messages in a mixed buffer.
+#[test]
+fn mixed_buffer() {
+    let bytes = [
+        0x90, 60, 100, 61, 0xF8, 0, 0xF0, 0x41, 0x10, 0xF7, 0xC0, 5, 0x10, 0xF6, 0xF2, 1,
+    ];
+    let spans = [0..3, 4..5, 3..6, 6..10, 10..12, 12..13, 13..14];
+
+    let result: Vec<_> = message_spans(&bytes).collect();
+    let expected: Vec<_> = spans.iter().cloned().map(Ok).collect();
+
+    assert_eq!(result, expected);
+}
+
+/// Data bytes without status byte.
+#[test]
+fn invalid_status() {
+    let bytes = [0x10, 0xF4, 0x20, 0xB0, 7, 90];
+
+    let result: Vec<_> = message_spans(&bytes).collect();
+
+    assert_eq!(
+        result,
+        [
+            Err(ParserError::InvalidStatus),
+            Err(ParserError::InvalidStatus),
+            Ok(3..6)
+        ]
+    );
+}
+
+/// Running status message expanded with its status byte.
+#[test]
+fn expanded_running_status() {
+    let bytes = [0x90, 60, 127, 61, 40];
+
+    let messages: Vec<_> = message_spans(&bytes)
+        .expanded()
+        .map(|message| message.unwrap().as_bytes().to_vec())
+        .collect();
+
+    assert_eq!(messages, [[0x90, 60, 127], [0x90, 61, 40]]);
+}
+
+/// Realtime message inside a message and SysEx message in expanded mode.
+#[test]
+fn expanded_mixed() {
+    let bytes = [0xB0, 7, 0xF8, 90, 0xF0, 0x41, 0xF7, 0x10];
+
+    let messages: Vec<_> = message_spans(&bytes).expanded().collect();
+
+    assert_eq!(
+        messages,
+        [
+            Ok(ExpandedMessage::Short([0xF8, 0, 0], 1)),
+            Ok(ExpandedMessage::Short([0xB0, 7, 90], 3)),
+            Ok(ExpandedMessage::SysEx(&[0xF0, 0x41, 0xF7])),
+            Err(ParserError::InvalidStatus),
+        ]
+    );
+}
+
+/// Realtime message inside a SysEx message in expanded mode.
+#[test]
+fn expanded_sysex_realtime() {
+    let bytes = [0xF0, 0x41, 0xF8, 0x10, 0xF7];
+
+    let messages: Vec<_> = message_spans(&bytes).expanded().collect();
+
+    assert_eq!(
+        messages,
+        [
+            Ok(ExpandedMessage::Short([0xF8, 0, 0], 1)),
+            Ok(ExpandedMessage::SysEx(&[0xF0, 0x41, 0xF8, 0x10, 0xF7])),
+        ]
+    );
+}
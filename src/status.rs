@@ -0,0 +1,123 @@
+//! Classification of status and data bytes.
+
+/// Largest data byte.
+pub const DATA_MAX: u8 = 0x7F;
+
+/// Smallest status byte of a channel voice message.
+pub const STATUS_CHANNEL_MIN: u8 = 0x80;
+
+/// Largest status byte of a channel voice message.
+pub const STATUS_CHANNEL_MAX: u8 = 0xEF;
+
+/// Start of SysEx.
+pub const SYSEX_START: u8 = 0xF0;
+
+/// Smallest status byte of a system common message.
+pub const SYSTEM_COMMON_MIN: u8 = 0xF1;
+
+/// Largest status byte of a system common message.
+pub const SYSTEM_COMMON_MAX: u8 = 0xF6;
+
+/// End of SysEx.
+pub const SYSEX_END: u8 = 0xF7;
+
+/// Smallest status byte of a system realtime message.
+pub const REALTIME_MIN: u8 = 0xF8;
+
+/// Largest status byte of a system realtime message.
+pub const REALTIME_MAX: u8 = 0xFF;
+
+/// Category of a single byte in a MIDI stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteClass {
+    /// Data byte in the range `0x00..=0x7F`.
+    Data,
+
+    /// Status byte of a channel voice message in the range `0x80..=0xEF`.
+    ChannelVoiceStatus,
+
+    /// Status byte of a system common message in the range `0xF1..=0xF6`.
+    SystemCommonStatus,
+
+    /// Status byte of a system realtime message in the range `0xF8..=0xFF`.
+    SystemRealtimeStatus,
+
+    /// Start of SysEx `0xF0`.
+    SysExStart,
+
+    /// End of SysEx `0xF7`.
+    SysExEnd,
+}
+
+/// Category and length of a message as derived from its status byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusInfo {
+    /// Category of the byte.
+    pub class: ByteClass,
+
+    /// Length of the complete message including the status byte,
+    /// `None` for data bytes, SysEx and undefined system common status bytes.
+    pub length: Option<usize>,
+}
+
+/// Returns the category of a byte based on its value only.
+pub const fn classify(byte: u8) -> ByteClass {
+    match byte {
+        0..=DATA_MAX => ByteClass::Data,
+        STATUS_CHANNEL_MIN..=STATUS_CHANNEL_MAX => ByteClass::ChannelVoiceStatus,
+        SYSEX_START => ByteClass::SysExStart,
+        SYSEX_END => ByteClass::SysExEnd,
+        SYSTEM_COMMON_MIN..=SYSTEM_COMMON_MAX => ByteClass::SystemCommonStatus,
+        REALTIME_MIN..=REALTIME_MAX => ByteClass::SystemRealtimeStatus,
+    }
+}
+
+/// Number of data bytes following each status byte in a complete message.
+/// Data bytes, SysEx, undefined and system realtime status bytes are listed with 0.
+const DATA_LENGTHS: [u8; 256] = data_lengths();
+
+/// Returns the table of data byte counts.
+const fn data_lengths() -> [u8; 256] {
+    let mut lengths = [0; 256];
+
+    let mut status = 0x80;
+    while status < 0xF0 {
+        lengths[status] = match status & 0xF0 {
+            0xC0 | 0xD0 => 1,
+            _ => 2,
+        };
+        status += 1;
+    }
+
+    lengths[0xF1] = 1;
+    lengths[0xF2] = 2;
+    lengths[0xF3] = 1;
+
+    lengths
+}
+
+/// Returns the number of data bytes following a status byte.
+const fn data_length(status: u8) -> usize {
+    DATA_LENGTHS[status as usize] as usize
+}
+
+/// Returns the length of a complete message including the status byte, or `None` for
+/// data bytes, SysEx, which has a variable length, and the undefined system common
+/// status bytes `0xF4` and `0xF5`, which don't start a message.
+pub const fn expected_message_length(status: u8) -> Option<usize> {
+    match status {
+        0x00..=0x7F | 0xF0 | 0xF4 | 0xF5 | 0xF7 => None,
+        _ => Some(data_length(status) + 1),
+    }
+}
+
+/// Returns the category and expected message length of a byte.
+pub const fn decode_status(byte: u8) -> StatusInfo {
+    StatusInfo {
+        class: classify(byte),
+        length: expected_message_length(byte),
+    }
+}
+
+#[cfg(test)]
+mod tests;
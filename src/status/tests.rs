@@ -0,0 +1,96 @@
+//! Unit tests
+
+use super::*;
+
+/// Classification at the range boundaries.
+#[test]
+fn classify_boundaries() {
+    let classes = [
+        (0x00, ByteClass::Data),
+        (0x7F, ByteClass::Data),
+        (0x80, ByteClass::ChannelVoiceStatus),
+        (0xEF, ByteClass::ChannelVoiceStatus),
+        (0xF0, ByteClass::SysExStart),
+        (0xF1, ByteClass::SystemCommonStatus),
+        (0xF6, ByteClass::SystemCommonStatus),
+        (0xF7, ByteClass::SysExEnd),
+        (0xF8, ByteClass::SystemRealtimeStatus),
+        (0xFF, ByteClass::SystemRealtimeStatus),
+    ];
+
+    for (byte, class) in classes {
+        assert_eq!(classify(byte), class);
+    }
+}
+
+/// Expected message lengths for all status bytes.
+#[test]
+fn expected_message_lengths() {
+    for byte in 0..=0xFF {
+        let length = match byte {
+            0x00..=0x7F | 0xF0 | 0xF4 | 0xF5 | 0xF7 => None,
+            0xC0..=0xDF | 0xF1 | 0xF3 => Some(2),
+            0x80..=0xEF | 0xF2 => Some(3),
+            _ => Some(1),
+        };
+        assert_eq!(expected_message_length(byte), length);
+    }
+}
+
+/// Category and length of representative bytes.
+#[test]
+fn decode_representative() {
+    let infos = [
+        (0x40, ByteClass::Data, None),
+        (0x90, ByteClass::ChannelVoiceStatus, Some(3)),
+        (0xC5, ByteClass::ChannelVoiceStatus, Some(2)),
+        (0xF0, ByteClass::SysExStart, None),
+        (0xF2, ByteClass::SystemCommonStatus, Some(3)),
+        (0xF3, ByteClass::SystemCommonStatus, Some(2)),
+        (0xF6, ByteClass::SystemCommonStatus, Some(1)),
+        (0xF7, ByteClass::SysExEnd, None),
+        (0xF8, ByteClass::SystemRealtimeStatus, Some(1)),
+    ];
+
+    for (byte, class, length) in infos {
+        assert_eq!(decode_status(byte), StatusInfo { class, length });
+    }
+}
+
+/// Constants at the range boundaries used by the parser.
+#[test]
+fn constants() {
+    assert_eq!(classify(DATA_MAX), ByteClass::Data);
+    assert_eq!(classify(DATA_MAX + 1), ByteClass::ChannelVoiceStatus);
+    assert_eq!(classify(STATUS_CHANNEL_MIN), ByteClass::ChannelVoiceStatus);
+    assert_eq!(classify(STATUS_CHANNEL_MAX), ByteClass::ChannelVoiceStatus);
+    assert_eq!(classify(STATUS_CHANNEL_MAX + 1), ByteClass::SysExStart);
+    assert_eq!(classify(SYSEX_START), ByteClass::SysExStart);
+    assert_eq!(classify(SYSTEM_COMMON_MIN), ByteClass::SystemCommonStatus);
+    assert_eq!(classify(SYSTEM_COMMON_MAX), ByteClass::SystemCommonStatus);
+    assert_eq!(classify(SYSEX_END), ByteClass::SysExEnd);
+    assert_eq!(classify(REALTIME_MIN), ByteClass::SystemRealtimeStatus);
+    assert_eq!(classify(REALTIME_MAX), ByteClass::SystemRealtimeStatus);
+
+    assert_eq!(SYSEX_START + 1, SYSTEM_COMMON_MIN);
+    assert_eq!(SYSTEM_COMMON_MAX + 1, SYSEX_END);
+    assert_eq!(SYSEX_END + 1, REALTIME_MIN);
+
+    let mut parser = crate::MidiStreamParser::<16>::new();
+    assert!(parser.parse(DATA_MAX).is_err());
+    assert_eq!(parser.parse(STATUS_CHANNEL_MAX).unwrap(), None);
+    assert_eq!(parser.parse(DATA_MAX).unwrap(), None);
+    assert_eq!(
+        parser.parse(DATA_MAX).unwrap(),
+        Some([STATUS_CHANNEL_MAX, DATA_MAX, DATA_MAX].as_ref())
+    );
+    assert_eq!(
+        parser.parse(REALTIME_MIN).unwrap(),
+        Some([REALTIME_MIN].as_ref())
+    );
+    assert_eq!(parser.parse(SYSEX_START).unwrap(), None);
+    assert_eq!(
+        parser.parse(SYSEX_END).unwrap(),
+        Some([SYSEX_START, SYSEX_END].as_ref())
+    );
+}
@@ -0,0 +1,215 @@
+//! Helpers for SysEx messages.
+
+use core::fmt;
+use core::ops::Range;
+
+/// Manufacturer ID of a SysEx message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManufacturerId {
+    /// Single byte ID.
+    Standard(u8),
+
+    /// Extended ID consisting of the two bytes following `0x00`.
+    Extended(u8, u8),
+}
+
+/// Returns the manufacturer ID of a complete SysEx message starting with `0xF0`.
+pub fn manufacturer_id(msg: &[u8]) -> Option<ManufacturerId> {
+    match *msg {
+        [0xF0, 0x00, high, low, ..] if high < 0x80 && low < 0x80 => {
+            Some(ManufacturerId::Extended(high, low))
+        }
+        [0xF0, 0x00, ..] => None,
+        [0xF0, id, ..] if id < 0x80 => Some(ManufacturerId::Standard(id)),
+        _ => None,
+    }
+}
+
+/// Returns the index range of the bytes between the manufacturer ID and the end of SysEx
+/// of a complete SysEx message, e.g. for checksum calculations. Returns `None` if the
+/// message doesn't start with `0xF0`, end with `0xF7` or contain a valid manufacturer ID.
+pub fn sysex_payload_range(msg: &[u8]) -> Option<Range<usize>> {
+    let start = match manufacturer_id(msg)? {
+        ManufacturerId::Standard(_) => 2,
+        ManufacturerId::Extended(_, _) => 4,
+    };
+
+    match msg.last() {
+        Some(0xF7) if msg.len() > start => Some(start..msg.len() - 1),
+        _ => None,
+    }
+}
+
+/// Complete SysEx message with accessors for its parts, borrowing the message bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SysEx<'a> {
+    /// Message bytes including start and end of SysEx.
+    bytes: &'a [u8],
+}
+
+impl<'a> SysEx<'a> {
+    /// Returns a wrapper for a complete SysEx message starting with `0xF0` and ending
+    /// with `0xF7`, or `None` if `msg` has a different form.
+    pub fn new(msg: &'a [u8]) -> Option<Self> {
+        match *msg {
+            [0xF0, .., 0xF7] => Some(Self { bytes: msg }),
+            _ => None,
+        }
+    }
+
+    /// Returns the complete message bytes.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the manufacturer ID, see [`manufacturer_id`].
+    pub fn manufacturer_id(&self) -> Option<ManufacturerId> {
+        manufacturer_id(self.bytes)
+    }
+
+    /// Returns the device ID of a universal SysEx message (`0xF0 0x7E` or `0xF0 0x7F`).
+    /// Manufacturer specific messages return `None`, as the position of their device ID
+    /// is not standardized.
+    pub fn device_id(&self) -> Option<u8> {
+        match *self.bytes {
+            [0xF0, 0x7E | 0x7F, device_id, _, ..] if device_id < 0x80 => Some(device_id),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes between the manufacturer ID and the end of SysEx.
+    /// For universal messages, this starts with the device ID.
+    pub fn payload(&self) -> &'a [u8] {
+        sysex_payload_range(self.bytes).map_or(&[], |range| &self.bytes[range])
+    }
+}
+
+/// Bytes displayed as space-separated hex values without allocation, e.g. `F0 41 10 F7`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexBytes<'a>(pub &'a [u8]);
+
+impl fmt::Display for HexBytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{:02X}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Handler for SysEx messages, called with a user context and the complete message.
+pub type SysExHandler<C> = fn(&mut C, &[u8]);
+
+/// Router dispatching complete SysEx messages to handlers by manufacturer ID.
+/// Holds up to `N` routes without allocation.
+pub struct SysExRouter<C, const N: usize> {
+    /// Registered handlers.
+    routes: [Option<(ManufacturerId, SysExHandler<C>)>; N],
+
+    /// Handler for messages without a registered manufacturer ID.
+    fallback: Option<SysExHandler<C>>,
+}
+
+impl<C, const N: usize> SysExRouter<C, N> {
+    /// Returns a new router with an optional handler for unknown manufacturer IDs.
+    pub fn new(fallback: Option<SysExHandler<C>>) -> Self {
+        Self {
+            routes: [None; N],
+            fallback,
+        }
+    }
+
+    /// Register a handler for a manufacturer ID, replacing a previous one for the same ID.
+    /// Returns `false` if all `N` routes are in use.
+    pub fn register(&mut self, id: ManufacturerId, handler: SysExHandler<C>) -> bool {
+        let slot = self
+            .routes
+            .iter()
+            .position(|route| matches!(route, Some((route_id, _)) if *route_id == id))
+            .or_else(|| self.routes.iter().position(Option::is_none));
+
+        match slot {
+            Some(slot) => {
+                self.routes[slot] = Some((id, handler));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Call the handler registered for the manufacturer ID of a complete SysEx message,
+    /// or the fallback handler otherwise. Returns `true` if a handler was called.
+    pub fn dispatch(&self, msg: &[u8], context: &mut C) -> bool {
+        let id = manufacturer_id(msg);
+        let handler = self
+            .routes
+            .iter()
+            .flatten()
+            .find(|(route_id, _)| Some(*route_id) == id)
+            .map(|(_, handler)| *handler)
+            .or(self.fallback);
+
+        match handler {
+            Some(handler) => {
+                handler(context, msg);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Error reported by a [`SysExSink`] that can't take more bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SinkError;
+
+/// Destination for SysEx payload bytes streamed by
+/// [`MidiStreamParser::parse_to_sink`](crate::MidiStreamParser::parse_to_sink).
+pub trait SysExSink {
+    /// Write payload bytes as they arrive.
+    fn write(&mut self, bytes: &[u8]) -> Result<(), SinkError>;
+
+    /// Called on the end of SysEx after all payload bytes were written.
+    fn finish(&mut self) -> Result<(), SinkError> {
+        Ok(())
+    }
+
+    /// Called instead of [`finish`](Self::finish) when a status byte other than realtime
+    /// interrupts the SysEx message, so the payload bytes written so far can be discarded.
+    fn abort(&mut self) {}
+}
+
+/// Compute the Roland checksum over `buf[data_range]` and write it directly after the range.
+/// Returns the length of the message up to and including the checksum, which is the
+/// index to write the end of SysEx byte to.
+///
+/// The checksum is the 7-bit two's complement of the sum of all bytes in the range.
+///
+/// # Panics
+///
+/// Panics if the range or the checksum position is out of bounds of `buf`.
+pub fn append_roland_checksum(buf: &mut [u8], data_range: Range<usize>) -> usize {
+    let end = data_range.end;
+    let sum = buf[data_range]
+        .iter()
+        .fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+
+    buf[end] = 0x80u8.wrapping_sub(sum & 0x7F) & 0x7F;
+
+    end + 1
+}
+
+/// Returns a 64-bit FNV-1a hash of a complete SysEx message.
+/// The hash is stable across platforms and versions, so it can be used as cache key.
+pub fn sysex_hash(msg: &[u8]) -> u64 {
+    msg.iter().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+#[cfg(test)]
+mod tests;
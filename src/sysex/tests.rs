@@ -0,0 +1,163 @@
+//! Unit tests
+
+use super::*;
+
+/// GS reset message with address 40 00 7F and data 00.
+#[test]
+fn roland_checksum() {
+    let mut buf = [0xF0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7F, 0x00, 0, 0];
+
+    let length = append_roland_checksum(&mut buf, 5..9);
+    buf[length] = 0xF7;
+
+    assert_eq!(length, 10);
+    assert_eq!(
+        buf,
+        [0xF0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7F, 0x00, 0x41, 0xF7]
+    );
+}
+
+/// Checksum of data summing up to a multiple of 128.
+#[test]
+fn roland_checksum_zero() {
+    let mut buf = [0x40, 0x40, 0x7F];
+
+    assert_eq!(append_roland_checksum(&mut buf, 0..2), 3);
+    assert_eq!(buf[2], 0);
+}
+
+/// Standard and extended manufacturer IDs.
+#[test]
+fn manufacturer_ids() {
+    assert_eq!(
+        manufacturer_id(&[0xF0, 0x41, 0x10, 0xF7]),
+        Some(ManufacturerId::Standard(0x41))
+    );
+    assert_eq!(
+        manufacturer_id(&[0xF0, 0x00, 0x20, 0x29, 0x01, 0xF7]),
+        Some(ManufacturerId::Extended(0x20, 0x29))
+    );
+    assert_eq!(manufacturer_id(&[0xF0, 0x00, 0x20]), None);
+    assert_eq!(manufacturer_id(&[0xF0, 0xF7]), None);
+    assert_eq!(manufacturer_id(&[0x90, 0x41]), None);
+}
+
+/// Roland and Yamaha messages routed to different handlers.
+#[test]
+fn router() {
+    let mut router = SysExRouter::<Vec<&str>, 4>::new(Some(|log, _| log.push("unknown")));
+
+    assert!(router.register(ManufacturerId::Standard(0x41), |log, _| log.push("roland")));
+    assert!(router.register(ManufacturerId::Standard(0x43), |log, _| log.push("yamaha")));
+
+    let mut log = Vec::new();
+    router.dispatch(&[0xF0, 0x43, 0x10, 0xF7], &mut log);
+    router.dispatch(&[0xF0, 0x41, 0x10, 0xF7], &mut log);
+    router.dispatch(&[0xF0, 0x00, 0x20, 0x29, 0xF7], &mut log);
+
+    assert_eq!(log, ["yamaha", "roland", "unknown"]);
+}
+
+/// Registering more routes than available.
+#[test]
+fn router_full() {
+    let mut router = SysExRouter::<u32, 1>::new(None);
+
+    assert!(router.register(ManufacturerId::Standard(0x41), |count, _| *count += 1));
+    assert!(router.register(ManufacturerId::Standard(0x41), |count, _| *count += 10));
+    assert!(!router.register(ManufacturerId::Standard(0x43), |count, _| *count += 1));
+
+    let mut count = 0;
+    assert!(router.dispatch(&[0xF0, 0x41, 0xF7], &mut count));
+    assert!(!router.dispatch(&[0xF0, 0x43, 0xF7], &mut count));
+    assert_eq!(count, 10);
+}
+
+/// Identical dumps hash equal, a changed byte hashes differently.
+#[test]
+fn hash() {
+    let dump = [
+        0xF0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7F, 0x00, 0x41, 0xF7,
+    ];
+    let copy = dump;
+    let mut changed = dump;
+    changed[7] = 0x7E;
+
+    assert_eq!(sysex_hash(&dump), sysex_hash(&copy));
+    assert_ne!(sysex_hash(&dump), sysex_hash(&changed));
+
+    // Reference values of FNV-1a.
+    assert_eq!(sysex_hash(&[]), 0xCBF2_9CE4_8422_2325);
+    assert_eq!(sysex_hash(b"a"), 0xAF63_DC4C_8601_EC8C);
+}
+
+/// Accessors of the SysEx wrapper for different headers.
+#[test]
+fn sysex_wrapper() {
+    let sysex = SysEx::new(&[0xF0, 0x41, 0x10, 0x42, 0x12, 0xF7]).unwrap();
+    assert_eq!(
+        sysex.manufacturer_id(),
+        Some(ManufacturerId::Standard(0x41))
+    );
+    assert_eq!(sysex.device_id(), None);
+    assert_eq!(sysex.payload(), [0x10, 0x42, 0x12]);
+
+    let sysex = SysEx::new(&[0xF0, 0x00, 0x20, 0x33, 0x01, 0xF7]).unwrap();
+    assert_eq!(
+        sysex.manufacturer_id(),
+        Some(ManufacturerId::Extended(0x20, 0x33))
+    );
+    assert_eq!(sysex.payload(), [0x01]);
+
+    let sysex = SysEx::new(&[0xF0, 0x7E, 0x05, 0x06, 0x01, 0xF7]).unwrap();
+    assert_eq!(
+        sysex.manufacturer_id(),
+        Some(ManufacturerId::Standard(0x7E))
+    );
+    assert_eq!(sysex.device_id(), Some(0x05));
+    assert_eq!(sysex.payload(), [0x05, 0x06, 0x01]);
+
+    let sysex = SysEx::new(&[0xF0, 0xF7]).unwrap();
+    assert_eq!(sysex.manufacturer_id(), None);
+    assert_eq!(sysex.payload(), []);
+
+    assert_eq!(SysEx::new(&[0xF0, 0x41]), None);
+    assert_eq!(SysEx::new(&[0x90, 0xF7]), None);
+}
+
+/// Hex representation of SysEx bytes.
+#[test]
+fn hex_bytes() {
+    let text = format!("{}", HexBytes(&[0xF0, 0x41, 0x0A, 0x7F, 0xF7]));
+    assert_eq!(text, "F0 41 0A 7F F7");
+
+    assert_eq!(format!("{}", HexBytes(&[])), "");
+}
+
+/// Payload ranges of messages with single byte and extended manufacturer IDs.
+#[test]
+fn payload_range() {
+    let msg = [0xF0, 0x41, 0x10, 0x42, 0x12, 0x40, 0xF7];
+    assert_eq!(sysex_payload_range(&msg), Some(2..6));
+
+    let msg = [0xF0, 0x00, 0x20, 0x33, 0x01, 0x02, 0xF7];
+    assert_eq!(sysex_payload_range(&msg), Some(4..6));
+
+    assert_eq!(sysex_payload_range(&[0xF0, 0x41, 0xF7]), Some(2..2));
+    assert_eq!(
+        sysex_payload_range(&[0xF0, 0x00, 0x20, 0x33, 0xF7]),
+        Some(4..4)
+    );
+    assert_eq!(sysex_payload_range(&[0xF0, 0x00, 0x20, 0xF7]), None);
+    assert_eq!(sysex_payload_range(&[0xF0, 0x41, 0x10]), None);
+    assert_eq!(sysex_payload_range(&[0xF0, 0xF7]), None);
+
+    // Range usable for the Roland checksum.
+    let mut msg = [
+        0xF0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7F, 0x00, 0x00, 0xF7,
+    ];
+    let range = sysex_payload_range(&msg).unwrap();
+    let data_range = range.start + 3..range.end - 1;
+    assert_eq!(append_roland_checksum(&mut msg, data_range), 10);
+    assert_eq!(msg[9], 0x41);
+}
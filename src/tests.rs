@@ -2,6 +2,22 @@
 
 use super::*;
 
+/// Pseudo random number generator (xorshift), seeded for reproducible streams.
+pub(crate) struct Random(pub(crate) u32);
+
+impl Random {
+    pub(crate) fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+
+    pub(crate) fn below(&mut self, limit: u32) -> u8 {
+        (self.next() % limit) as u8
+    }
+}
+
 /// Two note on messages sharing the same status byte.
 #[test]
 fn running_status() {
@@ -22,6 +38,66 @@ fn running_status() {
     }
 }
 
+/// Clock between two note on messages sharing the same status byte.
+#[test]
+fn running_status_with_realtime() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    let bytes = [0x90, 60, 127, 0xF8, 61, 40];
+    let messages = [
+        None,
+        None,
+        Some([0x90, 60, 127].as_ref()),
+        Some([0xF8].as_ref()),
+        None,
+        Some([0x90, 61, 40].as_ref()),
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+    assert!(parser.last_used_running_status());
+}
+
+/// Channel pressure as 2-byte message, also with running status.
+#[test]
+fn channel_pressure() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    let bytes = [0xD0, 0x40, 0x50];
+    let messages = [
+        None,
+        Some([0xD0, 0x40].as_ref()),
+        Some([0xD0, 0x50].as_ref()),
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+}
+
+/// Poly pressure as 3-byte message, also with running status.
+#[test]
+fn poly_pressure() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    let bytes = [0xA0, 60, 80, 61, 90];
+    let messages = [
+        None,
+        None,
+        Some([0xA0, 60, 80].as_ref()),
+        None,
+        Some([0xA0, 61, 90].as_ref()),
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+}
+
 /// SysEx message without anything special.
 #[test]
 fn sysex() {
@@ -97,3 +173,2154 @@ fn sysex_overflow() {
         }
     }
 }
+
+/// Parser must stay usable across threads.
+#[test]
+fn send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<MidiStreamParser<0>>();
+    assert_send_sync::<MidiStreamParser<256>>();
+    assert_send_sync::<ParserError>();
+}
+
+/// Song position pointer with both data bytes and no running status.
+#[test]
+fn song_position() {
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        spec_compliant: true,
+        ..Default::default()
+    });
+
+    let bytes = [0xF2, 0x10, 0x20];
+    let messages = [None, None, Some([0xF2, 0x10, 0x20].as_ref())];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+
+    assert!(matches!(
+        parser.parse(0x30),
+        Err(ParserError::InvalidStatus)
+    ));
+}
+
+/// Song position pointer interrupted by a note on message.
+#[test]
+fn song_position_truncated() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    let bytes = [0xF2, 0x10, 0x90, 60, 127];
+    let messages = [None, None, None, None, Some([0x90, 60, 127].as_ref())];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        strict: true,
+        ..Default::default()
+    });
+
+    assert_eq!(parser.parse(0xF2).unwrap(), None);
+    assert_eq!(parser.parse(0x10).unwrap(), None);
+    assert!(matches!(
+        parser.parse(0x90),
+        Err(ParserError::TruncatedMessage)
+    ));
+    assert_eq!(parser.parse(60).unwrap(), None);
+    assert_eq!(parser.parse(127).unwrap(), Some([0x90, 60, 127].as_ref()));
+}
+
+/// Channel and system common messages interrupted by a status byte in strict mode.
+#[test]
+fn messages_truncated() {
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig::strict());
+
+    assert_eq!(parser.parse(0x90).unwrap(), None);
+    assert_eq!(parser.parse(60).unwrap(), None);
+    assert!(matches!(
+        parser.parse(0x80),
+        Err(ParserError::TruncatedMessage)
+    ));
+    assert_eq!(parser.parse(60).unwrap(), None);
+    assert_eq!(parser.parse(0).unwrap(), Some([0x80, 60, 0].as_ref()));
+
+    assert_eq!(parser.parse(0xF3).unwrap(), None);
+    assert!(matches!(
+        parser.parse(0x90),
+        Err(ParserError::TruncatedMessage)
+    ));
+    assert_eq!(parser.parse(60).unwrap(), None);
+    assert_eq!(parser.parse(1).unwrap(), Some([0x90, 60, 1].as_ref()));
+
+    // Status byte kept for running status is not truncated.
+    assert_eq!(parser.parse(0xC0).unwrap(), None);
+    assert_eq!(parser.parse(5).unwrap(), Some([0xC0, 5].as_ref()));
+    assert_eq!(parser.parse(0xF1).unwrap(), None);
+    assert_eq!(parser.parse(0x20).unwrap(), Some([0xF1, 0x20].as_ref()));
+}
+
+/// Pending state while feeding partial and complete messages.
+#[test]
+fn pending_state() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    assert_eq!(parser.pending_len(), 0);
+    assert!(parser.is_idle());
+
+    let bytes = [0x90, 60, 127, 61];
+    let pending = [1, 2, 1, 2];
+
+    for (byte, pending) in bytes.iter().zip(pending.iter()) {
+        parser.parse(*byte).unwrap();
+        assert_eq!(parser.pending_len(), *pending);
+        assert!(!parser.is_idle());
+    }
+
+    let bytes = [0xF0, 0x10, 0x20];
+    let pending = [1, 2, 3];
+
+    for (byte, pending) in bytes.iter().zip(pending.iter()) {
+        parser.parse(*byte).unwrap();
+        assert_eq!(parser.pending_len(), *pending);
+        assert!(!parser.is_idle());
+    }
+
+    parser.parse(0xF7).unwrap();
+    assert_eq!(parser.pending_len(), 0);
+    assert!(parser.is_idle());
+}
+
+/// MTC quarter frame and song select don't use running status.
+#[test]
+fn system_common_no_running_status() {
+    for bytes in [[0xF1, 0x20, 0x30], [0xF3, 0x05, 0x06]] {
+        let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+            spec_compliant: true,
+            ..Default::default()
+        });
+
+        assert_eq!(parser.parse(bytes[0]).unwrap(), None);
+        assert_eq!(parser.parse(bytes[1]).unwrap(), Some(&bytes[0..2]));
+        assert!(matches!(
+            parser.parse(bytes[2]),
+            Err(ParserError::InvalidStatus)
+        ));
+    }
+}
+
+/// SysEx message without payload.
+#[test]
+fn sysex_empty() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    assert_eq!(parser.parse(0xF0).unwrap(), None);
+    assert_eq!(parser.parse(0xF7).unwrap(), Some([0xF0, 0xF7].as_ref()));
+
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        strict: true,
+        ..Default::default()
+    });
+
+    assert_eq!(parser.parse(0xF0).unwrap(), None);
+    assert!(matches!(parser.parse(0xF7), Err(ParserError::EmptySysEx)));
+}
+
+/// Restoring a snapshot taken in the middle of a SysEx message.
+#[test]
+fn snapshot_restore() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    for byte in [0xF0, 0x10, 0x20] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+
+    let state = parser.snapshot();
+
+    for byte in [0xF7, 0x90, 60, 0xF0, 0x55, 0x66, 0x77] {
+        parser.parse(byte).ok();
+    }
+
+    parser.restore(&state);
+
+    assert_eq!(parser.parse(0x30).unwrap(), None);
+    assert_eq!(
+        parser.parse(0xF7).unwrap(),
+        Some([0xF0, 0x10, 0x20, 0x30, 0xF7].as_ref())
+    );
+}
+
+/// Typed parsing keeping only note messages.
+#[test]
+fn message_filter() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    parser.set_message_filter(Some(MessageFilter {
+        predicate: |message| {
+            matches!(
+                message,
+                MidiMessage::NoteOn { .. } | MidiMessage::NoteOff { .. }
+            )
+        },
+        exempt_realtime: true,
+    }));
+
+    let bytes = [0x90, 60, 127, 0xB0, 7, 100, 0xF8, 0xC0, 5, 0x80, 60, 0];
+    let messages = [
+        None,
+        None,
+        Some(MidiMessage::NoteOn {
+            channel: 0,
+            note: 60,
+            velocity: 127,
+        }),
+        None,
+        None,
+        None,
+        Some(MidiMessage::SystemRealtime(
+            SystemRealtimeMessage::TimingClock,
+        )),
+        None,
+        None,
+        None,
+        None,
+        Some(MidiMessage::NoteOff {
+            channel: 0,
+            note: 60,
+            velocity: 0,
+        }),
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse_typed(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+}
+
+/// Reset discards the message in progress and running status.
+#[test]
+fn reset() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    for byte in [0x90, 60, 127, 61] {
+        parser.parse(byte).unwrap();
+    }
+
+    parser.reset();
+
+    assert!(parser.is_idle());
+    assert!(matches!(parser.parse(40), Err(ParserError::InvalidStatus)));
+}
+
+/// SysEx dump split by a reset and a restart without end of SysEx.
+#[test]
+fn sysex_continuation() {
+    let mut parser = MidiStreamParser::<256>::new();
+    parser.set_sysex_continuation(Some(|fragment| fragment.len() < 8));
+
+    for byte in [0xF0, 0x41, 0x10, 0x20] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+
+    parser.reset();
+
+    for byte in [0xF0, 0x41, 0x30, 0xF0, 0x41, 0x40] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    assert_eq!(
+        parser.parse(0xF7).unwrap(),
+        Some([0xF0, 0x41, 0x10, 0x20, 0x30, 0x40, 0xF7].as_ref())
+    );
+
+    // Different manufacturer ID starts a new message.
+    for byte in [0xF0, 0x41, 0x10] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    for byte in [0xF0, 0x43, 0x50] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    assert_eq!(
+        parser.parse(0xF7).unwrap(),
+        Some([0xF0, 0x43, 0x50, 0xF7].as_ref())
+    );
+
+    // Hook rejecting the continuation.
+    parser.set_sysex_continuation(Some(|_| false));
+
+    for byte in [0xF0, 0x00, 0x20, 0x29, 0x10, 0xF0, 0x00, 0x20, 0x29, 0x30] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    assert_eq!(
+        parser.parse(0xF7).unwrap(),
+        Some([0xF0, 0x00, 0x20, 0x29, 0x30, 0xF7].as_ref())
+    );
+}
+
+/// Data bytes following each kind of message.
+/// Only channel messages establish running status, realtime messages don't affect it.
+#[test]
+fn data_after_message() {
+    let running: [(&[u8], Option<&[u8]>); 3] = [
+        (&[0x90, 60, 127], None),
+        (&[0xC0, 5], Some(&[0xC0, 0x10])),
+        (&[0xC0, 5, 0xF8], Some(&[0xC0, 0x10])),
+    ];
+
+    for (bytes, message) in running {
+        let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+            spec_compliant: true,
+            ..Default::default()
+        });
+
+        for byte in bytes {
+            parser.parse(*byte).unwrap();
+        }
+
+        assert_eq!(parser.parse(0x10).unwrap(), message);
+    }
+
+    let not_running: [&[u8]; 8] = [
+        &[0xF1, 0x20],
+        &[0xF2, 0x10, 0x20],
+        &[0xF3, 0x05],
+        &[0xF4],
+        &[0xF5],
+        &[0xF6],
+        &[0xF8],
+        &[0xF0, 0x10, 0xF7],
+    ];
+
+    for bytes in not_running {
+        let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+            spec_compliant: true,
+            ..Default::default()
+        });
+
+        for byte in bytes {
+            parser.parse(*byte).unwrap();
+        }
+
+        assert!(matches!(
+            parser.parse(0x10),
+            Err(ParserError::InvalidStatus)
+        ));
+    }
+}
+
+/// Tune request is a single byte message.
+#[test]
+fn tune_request() {
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        spec_compliant: true,
+        ..Default::default()
+    });
+
+    assert_eq!(parser.parse(0xF6).unwrap(), Some([0xF6].as_ref()));
+}
+
+/// Tune request emitted immediately and cancelling running status.
+#[test]
+fn tune_request_cancels_running_status() {
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        spec_compliant: true,
+        ..Default::default()
+    });
+
+    for byte in [0x90, 60] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    assert_eq!(parser.parse(100).unwrap(), Some([0x90, 60, 100].as_ref()));
+    assert_eq!(parser.parse(0xF6).unwrap(), Some([0xF6].as_ref()));
+
+    assert!(matches!(parser.parse(62), Err(ParserError::InvalidStatus)));
+    assert!(matches!(parser.parse(40), Err(ParserError::InvalidStatus)));
+}
+
+/// Routing of clock messages interleaved with notes.
+#[test]
+fn parse_split() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    let bytes = [0x90, 0xF8, 60, 127, 0xF8, 61, 0xF8, 40];
+    let mut realtime = Vec::new();
+    let mut messages = Vec::new();
+
+    for byte in bytes {
+        parser
+            .parse_split(
+                byte,
+                |status| realtime.push(status),
+                |message| messages.push(message.to_vec()),
+            )
+            .unwrap();
+    }
+
+    assert_eq!(realtime, [0xF8, 0xF8, 0xF8]);
+    assert_eq!(messages, [[0x90, 60, 127], [0x90, 61, 40]]);
+}
+
+/// Random byte streams keep the state consistent and never emit a data byte
+/// more often than it was received. Status bytes are not counted because
+/// running status repeats them in each message.
+#[test]
+fn random_stream() {
+    fn check<const N: usize>(seed: u32, config: ParserConfig) {
+        let mut parser = MidiStreamParser::<N>::with_config(config);
+        let mut random = Random(seed);
+        let mut input_bytes = 0;
+        let mut emitted_bytes = 0;
+
+        for _ in 0..100_000 {
+            let byte = random.below(0x100);
+
+            if byte < 0x80 {
+                input_bytes += 1;
+            }
+            if let Ok(Some(message)) = parser.parse(byte) {
+                emitted_bytes += message.iter().filter(|byte| **byte < 0x80).count();
+            }
+            parser.check_invariants();
+        }
+
+        assert!(emitted_bytes <= input_bytes);
+    }
+
+    for config in [ParserConfig::new(), ParserConfig::strict()] {
+        check::<0>(1, config);
+        check::<4>(2, config);
+        check::<256>(3, config);
+    }
+}
+
+/// End of SysEx without start is ignored.
+#[test]
+fn stray_sysex_end() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    for byte in [0xF0, 0x10, 0xF7] {
+        parser.parse(byte).unwrap();
+    }
+
+    assert_eq!(parser.parse(0xF7).unwrap(), None);
+}
+
+/// Start of SysEx reported before the complete message.
+#[test]
+fn sysex_start() {
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        emit_sysex_start: true,
+        ..Default::default()
+    });
+
+    let bytes = [0xF0, 0x10, 0x20, 0xF7];
+    let messages = [
+        Some(MidiMessage::SysExStart),
+        None,
+        None,
+        Some(MidiMessage::SysEx(&[0xF0, 0x10, 0x20, 0xF7])),
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse_typed(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+
+    assert_eq!(parser.parse(0xF0).unwrap(), Some([0xF0].as_ref()));
+}
+
+/// Undefined realtime status bytes in the middle of a note on message.
+#[test]
+fn undefined_realtime() {
+    for status in [0xF9, 0xFD] {
+        let mut parser = MidiStreamParser::<256>::new();
+
+        assert_eq!(parser.parse(0x90).unwrap(), None);
+        assert_eq!(parser.parse(60).unwrap(), None);
+        assert_eq!(parser.parse(status).unwrap(), Some([status].as_ref()));
+        assert_eq!(parser.parse(127).unwrap(), Some([0x90, 60, 127].as_ref()));
+
+        let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+            strict: true,
+            ..Default::default()
+        });
+
+        assert_eq!(parser.parse(0x90).unwrap(), None);
+        assert_eq!(parser.parse(60).unwrap(), None);
+        assert!(matches!(
+            parser.parse(status),
+            Err(ParserError::UndefinedStatus(byte)) if byte == status
+        ));
+        assert_eq!(parser.parse(127).unwrap(), Some([0x90, 60, 127].as_ref()));
+    }
+}
+
+/// Long SysEx message drained from the ring buffer in two chunks.
+#[test]
+fn sysex_ring_buffer() {
+    let mut parser = MidiStreamParser::<8>::with_config(ParserConfig {
+        sysex_ring_buffer: true,
+        ..Default::default()
+    });
+
+    let mut received = Vec::new();
+    let mut chunk = [0; 8];
+
+    for byte in [0xF0, 1, 2, 3, 4, 5, 6, 7] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+
+    let length = parser.take_sysex_chunk(&mut chunk);
+    received.extend_from_slice(&chunk[..length]);
+    assert_eq!(length, 8);
+
+    for byte in [8, 9, 10, 11] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    assert_eq!(
+        parser.parse_typed(0xF7).unwrap(),
+        Some(MidiMessage::SysExEnd)
+    );
+
+    let length = parser.take_sysex_chunk(&mut chunk);
+    received.extend_from_slice(&chunk[..length]);
+    assert_eq!(length, 5);
+
+    assert_eq!(received, [0xF0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 0xF7]);
+    assert_eq!(parser.take_sysex_chunk(&mut chunk), 0);
+}
+
+/// SysEx message interrupted in ring buffer mode, discarded before the next one.
+#[test]
+fn sysex_ring_buffer_interrupted() {
+    let mut parser = MidiStreamParser::<8>::with_config(ParserConfig {
+        sysex_ring_buffer: true,
+        spec_compliant: true,
+        ..Default::default()
+    });
+    let mut chunk = [0; 8];
+
+    let bytes = [0xF0, 1, 2, 0x90, 0x3C, 0x64, 0xF0, 3];
+    let messages = [
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some([0x90, 0x3C, 0x64].as_ref()),
+        None,
+        None,
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        assert_eq!(parser.parse(*byte).unwrap(), *message);
+    }
+
+    let mut state = [0; 16];
+    assert_eq!(parser.serialize_sysex_state(&mut state), 7);
+    assert_eq!(state[..7], [1, 2, 0, 0, 0, 0xF0, 3]);
+
+    let length = parser.take_sysex_chunk(&mut chunk);
+    assert_eq!(chunk[..length], [0xF0, 3]);
+}
+
+/// Running status usage of completed messages.
+#[test]
+fn last_used_running_status() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    let bytes = [0x90, 60, 127, 61, 40, 0xF8, 62, 30, 0xC0, 5, 6];
+    let used = [
+        false, false, false, false, true, true, true, true, true, false, true,
+    ];
+
+    for (byte, used) in bytes.iter().zip(used.iter()) {
+        parser.parse(*byte).unwrap();
+        assert_eq!(parser.last_used_running_status(), *used);
+    }
+}
+
+/// Clock message between the data bytes of 3-byte messages.
+#[test]
+fn realtime_between_data_bytes() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    let bytes = [0xE0, 0x00, 0xF8, 0x40, 0x90, 60, 0xF8, 127];
+    let messages = [
+        None,
+        None,
+        Some([0xF8].as_ref()),
+        Some([0xE0, 0x00, 0x40].as_ref()),
+        None,
+        None,
+        Some([0xF8].as_ref()),
+        Some([0x90, 60, 127].as_ref()),
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+}
+
+/// End of SysEx in ring buffer mode producing chunks and an end message.
+#[test]
+fn feed_sysex_end() {
+    let mut parser = MidiStreamParser::<4>::with_config(ParserConfig {
+        sysex_ring_buffer: true,
+        ..Default::default()
+    });
+    let mut chunk = [0; 3];
+
+    for byte in [0xF0, 1, 2] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    assert_eq!(parser.take_sysex_chunk(&mut chunk), 3);
+
+    for byte in [3, 4] {
+        let mut messages = Vec::new();
+        parser.feed(byte, &mut messages).unwrap();
+        assert!(messages.is_empty());
+    }
+
+    let mut messages = Vec::new();
+    parser.feed(0xF7, &mut messages).unwrap();
+    assert_eq!(
+        messages,
+        [
+            MidiMessage::SysExChunk(&[3]),
+            MidiMessage::SysExChunk(&[4, 0xF7]),
+            MidiMessage::SysExEnd
+        ]
+    );
+
+    parser.parse(0xC0).unwrap();
+    let mut messages = Vec::new();
+    parser.feed(5, &mut messages).unwrap();
+    assert_eq!(
+        messages,
+        [MidiMessage::ProgramChange {
+            channel: 0,
+            program: 5
+        }]
+    );
+}
+
+/// Data bytes at startup dropped until the first status byte.
+#[test]
+fn drop_until_first_status() {
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        drop_until_first_status: true,
+        ..Default::default()
+    });
+
+    let bytes = [60, 127, 0xF8, 61, 0x90, 62, 100];
+    let messages = [
+        None,
+        None,
+        Some([0xF8].as_ref()),
+        None,
+        None,
+        None,
+        Some([0x90, 62, 100].as_ref()),
+    ];
+
+    for (i, (byte, message)) in bytes.iter().zip(messages.iter()).enumerate() {
+        assert_eq!(parser.warmed_up(), i > 4);
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+
+    assert!(parser.warmed_up());
+
+    let mut parser = MidiStreamParser::<256>::new();
+
+    assert!(matches!(parser.parse(60), Err(ParserError::InvalidStatus)));
+    assert!(!parser.warmed_up());
+}
+
+/// Messages stored in a reused slot.
+#[test]
+fn parse_into_msg() {
+    let mut parser = MidiStreamParser::<256>::new();
+    let mut out = MidiMessage::SysExEnd;
+
+    let bytes = [0x90, 60, 100, 61, 0, 0xF8, 0xC0, 5];
+    let written = [false, false, true, false, true, true, false, true];
+    let messages = [
+        MidiMessage::SysExEnd,
+        MidiMessage::SysExEnd,
+        MidiMessage::NoteOn {
+            channel: 0,
+            note: 60,
+            velocity: 100,
+        },
+        MidiMessage::NoteOn {
+            channel: 0,
+            note: 60,
+            velocity: 100,
+        },
+        MidiMessage::NoteOn {
+            channel: 0,
+            note: 61,
+            velocity: 0,
+        },
+        MidiMessage::SystemRealtime(SystemRealtimeMessage::TimingClock),
+        MidiMessage::SystemRealtime(SystemRealtimeMessage::TimingClock),
+        MidiMessage::ProgramChange {
+            channel: 0,
+            program: 5,
+        },
+    ];
+
+    for ((byte, written), message) in bytes.iter().zip(written.iter()).zip(messages.iter()) {
+        assert_eq!(parser.parse_into_msg(*byte, &mut out), Ok(*written));
+        assert_eq!(out, *message);
+    }
+
+    for byte in [0xF0, 0x10] {
+        assert_eq!(parser.parse_into_msg(byte, &mut out), Ok(false));
+    }
+    assert_eq!(
+        parser.parse_into_msg(0xF7, &mut out),
+        Err(ParserError::BorrowedMessage)
+    );
+    assert_eq!(out, messages[7]);
+}
+
+/// Two SysEx messages following each other immediately.
+#[test]
+fn sysex_back_to_back() {
+    for config in [
+        ParserConfig::default(),
+        ParserConfig {
+            strict: true,
+            ..Default::default()
+        },
+    ] {
+        let mut parser = MidiStreamParser::<256>::with_config(config);
+
+        let bytes = [0xF0, 0x10, 0xF7, 0xF0, 0x20, 0xF7];
+        let messages = [
+            None,
+            None,
+            Some([0xF0, 0x10, 0xF7].as_ref()),
+            None,
+            None,
+            Some([0xF0, 0x20, 0xF7].as_ref()),
+        ];
+
+        for (byte, message) in bytes.iter().zip(messages.iter()) {
+            let result = parser.parse(*byte).unwrap();
+            assert_eq!(result, *message);
+        }
+
+        assert!(parser.is_idle());
+    }
+}
+
+/// Sink backed by a fixed array.
+#[derive(Default)]
+struct ArraySink {
+    data: [u8; 4],
+    len: usize,
+    finished: bool,
+    aborted: bool,
+}
+
+impl SysExSink for ArraySink {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), SinkError> {
+        let end = self.len + bytes.len();
+        if end > self.data.len() {
+            return Err(SinkError);
+        }
+        self.data[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), SinkError> {
+        self.finished = true;
+        Ok(())
+    }
+
+    fn abort(&mut self) {
+        self.aborted = true;
+        self.len = 0;
+    }
+}
+
+/// SysEx payload streamed into a sink without buffering.
+#[test]
+fn parse_to_sink() {
+    let mut parser = MidiStreamParser::<0>::new();
+    let mut sink = ArraySink::default();
+
+    let bytes = [0xF0, 0x10, 0x20, 0xF8, 0x30, 0xF7, 0x90, 60, 100];
+    let messages = [
+        None,
+        None,
+        None,
+        Some([0xF8].as_ref()),
+        None,
+        None,
+        None,
+        None,
+        Some([0x90, 60, 100].as_ref()),
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse_to_sink(*byte, &mut sink).unwrap();
+        assert_eq!(result, *message);
+    }
+
+    assert_eq!(&sink.data[..sink.len], [0x10, 0x20, 0x30]);
+    assert!(sink.finished);
+
+    let mut sink = ArraySink::default();
+
+    for byte in [0xF0, 1, 2, 3, 4] {
+        assert_eq!(parser.parse_to_sink(byte, &mut sink), Ok(None));
+    }
+    assert_eq!(
+        parser.parse_to_sink(5, &mut sink),
+        Err(ParserError::Sink(SinkError))
+    );
+}
+
+/// SysEx streamed into a sink interrupted by a channel message.
+#[test]
+fn parse_to_sink_interrupted() {
+    let mut parser = MidiStreamParser::<0>::with_config(ParserConfig {
+        spec_compliant: true,
+        ..Default::default()
+    });
+    let mut sink = ArraySink::default();
+
+    for byte in [0xF0, 1, 2] {
+        assert_eq!(parser.parse_to_sink(byte, &mut sink), Ok(None));
+    }
+    assert_eq!(&sink.data[..sink.len], [1, 2]);
+
+    assert_eq!(parser.parse_to_sink(0x90, &mut sink), Ok(None));
+    assert!(sink.aborted);
+    assert!(!sink.finished);
+
+    let bytes = [60, 100, 0xF0, 3, 4, 0xF7];
+    let messages = [None, Some([0x90, 60, 100].as_ref()), None, None, None, None];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse_to_sink(*byte, &mut sink).unwrap();
+        assert_eq!(result, *message);
+    }
+
+    assert_eq!(&sink.data[..sink.len], [3, 4]);
+    assert!(sink.finished);
+}
+
+/// End of SysEx streamed into a sink with running status, strict mode and device ID filter.
+#[test]
+fn parse_to_sink_end() {
+    let mut parser = MidiStreamParser::<4>::with_config(ParserConfig {
+        preserve_running_status_across_sysex: true,
+        ..Default::default()
+    });
+    let mut sink = ArraySink::default();
+
+    let bytes = [0x90, 60, 100, 0xF0, 0x10, 0xF7, 61, 40];
+    let messages = [
+        None,
+        None,
+        Some([0x90, 60, 100].as_ref()),
+        None,
+        None,
+        None,
+        None,
+        Some([0x90, 61, 40].as_ref()),
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse_to_sink(*byte, &mut sink).unwrap();
+        assert_eq!(result, *message);
+    }
+    assert!(sink.finished);
+
+    let mut parser = MidiStreamParser::<4>::with_config(ParserConfig {
+        sysex_device_id: Some(0),
+        ..ParserConfig::strict()
+    });
+
+    let mut sink = ArraySink::default();
+    assert_eq!(parser.parse_to_sink(0xF0, &mut sink), Ok(None));
+    assert_eq!(
+        parser.parse_to_sink(0xF7, &mut sink),
+        Err(ParserError::EmptySysEx)
+    );
+    assert!(!sink.finished);
+
+    let mut sink = ArraySink::default();
+    for byte in [0xF0, 0x7E, 0x05, 0x06, 0x01, 0xF7] {
+        assert_eq!(parser.parse_to_sink(byte, &mut sink), Ok(None));
+    }
+    assert!(sink.aborted);
+    assert!(!sink.finished);
+
+    let mut sink = ArraySink::default();
+    for byte in [0xF0, 0x7E, 0x00, 0x06, 0x01, 0xF7] {
+        assert_eq!(parser.parse_to_sink(byte, &mut sink), Ok(None));
+    }
+    assert_eq!(&sink.data[..sink.len], [0x7E, 0x00, 0x06, 0x01]);
+    assert!(sink.finished);
+}
+
+/// SysEx message as the very first bytes after creation.
+#[test]
+fn sysex_first_bytes() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    let bytes = [0xF0, 0x7E, 0x00, 0xF7];
+    let messages = [None, None, None, Some([0xF0, 0x7E, 0x00, 0xF7].as_ref())];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+
+    // No stale status byte left for running status.
+    assert!(matches!(
+        parser.parse(0x10),
+        Err(ParserError::InvalidStatus)
+    ));
+}
+
+/// Largest SysEx buffer usage tracked over messages of varying length.
+#[test]
+fn high_water_mark() {
+    let mut parser = MidiStreamParser::<8>::new();
+
+    assert_eq!(parser.capacity(), 8);
+    assert_eq!(parser.high_water_mark(), 0);
+
+    let sysex: [&[u8]; 3] = [
+        &[0xF0, 0x10, 0x20, 0xF7],
+        &[0xF0, 0x10, 0x20, 0x30, 0x40, 0xF7],
+        &[0xF0, 0x10, 0xF7],
+    ];
+    let high_water_marks = [4, 6, 6];
+
+    for (bytes, high_water_mark) in sysex.iter().zip(high_water_marks.iter()) {
+        for byte in *bytes {
+            parser.parse(*byte).unwrap();
+        }
+        assert_eq!(parser.high_water_mark(), *high_water_mark);
+    }
+
+    parser.reset();
+    assert_eq!(parser.high_water_mark(), 6);
+}
+
+/// Numeric error codes converted back into errors.
+#[test]
+fn error_codes() {
+    let errors = [
+        ParserError::InvalidStatus,
+        ParserError::SysexOverflow,
+        ParserError::TruncatedMessage,
+        ParserError::EmptySysEx,
+        ParserError::UndefinedStatus(0xF9),
+        ParserError::UndefinedStatus(0xFD),
+        ParserError::InvalidLength,
+        ParserError::InvalidData,
+        ParserError::InvalidMessageType,
+        ParserError::BorrowedMessage,
+        ParserError::Sink(SinkError),
+        ParserError::SysExDataCorrupt,
+    ];
+
+    for error in errors {
+        let code = u8::from(&error);
+        assert_eq!(ParserError::try_from(code), Ok(error));
+    }
+
+    assert_eq!(u8::from(&ParserError::UndefinedStatus(0xF9)), 0xF9);
+    assert_eq!(ParserError::try_from(0), Err(0));
+    assert_eq!(ParserError::try_from(0x7F), Err(0x7F));
+}
+
+/// Note on followed by note off messages using running status of `0x80`.
+#[test]
+fn note_on_note_off_running_status() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    let bytes = [0x90, 60, 100, 0x80, 60, 0, 62, 0];
+    let messages = [
+        None,
+        None,
+        Some([0x90, 60, 100].as_ref()),
+        None,
+        None,
+        Some([0x80, 60, 0].as_ref()),
+        None,
+        Some([0x80, 62, 0].as_ref()),
+    ];
+    let running_status = [false, false, false, false, false, false, false, true];
+
+    for ((byte, message), running_status) in
+        bytes.iter().zip(messages.iter()).zip(running_status.iter())
+    {
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+        if result.is_some() {
+            assert_eq!(parser.last_used_running_status(), *running_status);
+        }
+    }
+}
+
+/// Oversized SysEx message closed at the end of the buffer.
+#[test]
+fn truncate_sysex_on_overflow() {
+    let mut parser = MidiStreamParser::<4>::with_config(ParserConfig {
+        truncate_sysex_on_overflow: true,
+        ..Default::default()
+    });
+
+    let bytes = [
+        0xF0, 1, 2, 3, 4, 5, 0xF8, 6, 0xF7, 0x90, 60, 100, 0xF0, 7, 0xF7,
+    ];
+    let messages = [
+        None,
+        None,
+        None,
+        None,
+        Some([0xF0, 1, 2, 0xF7].as_ref()),
+        None,
+        Some([0xF8].as_ref()),
+        None,
+        None,
+        None,
+        None,
+        Some([0x90, 60, 100].as_ref()),
+        None,
+        None,
+        Some([0xF0, 7, 0xF7].as_ref()),
+    ];
+    let truncated = [
+        false, false, false, false, true, true, true, true, true, true, true, true, true, true,
+        false,
+    ];
+
+    for ((byte, message), truncated) in bytes.iter().zip(messages.iter()).zip(truncated.iter()) {
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+        assert_eq!(parser.last_sysex_truncated(), *truncated);
+    }
+
+    // Buffer filled exactly when the end of SysEx arrives.
+    for byte in [0xF0, 1, 2, 3] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    assert_eq!(
+        parser.parse(0xF7).unwrap(),
+        Some([0xF0, 1, 2, 0xF7].as_ref())
+    );
+    assert!(parser.last_sysex_truncated());
+    assert!(matches!(parser.parse(4), Err(ParserError::InvalidStatus)));
+}
+
+/// Messages completed after the expected number of data bytes for every status byte.
+#[test]
+fn message_lengths() {
+    for status in 0x80..=0xFF {
+        if status == 0xF0 {
+            continue;
+        }
+
+        let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+            spec_compliant: true,
+            ..Default::default()
+        });
+        let result = parser
+            .parse(status)
+            .map(|message| message.map(|m| m.to_vec()));
+
+        let data_length = match status {
+            0xC0..=0xDF | 0xF1 | 0xF3 => 1,
+            0x80..=0xEF | 0xF2 => 2,
+            0xF6 | 0xF8..=0xFF => {
+                assert_eq!(result, Ok(Some(vec![status])));
+                continue;
+            }
+            _ => {
+                assert_eq!(result, Ok(None));
+                assert!(matches!(
+                    parser.parse(0x10),
+                    Err(ParserError::InvalidStatus)
+                ));
+                continue;
+            }
+        };
+        assert_eq!(result, Ok(None));
+
+        let bytes = [0x10, 0x20, 0x30, 0x40];
+
+        for (i, byte) in bytes.iter().enumerate() {
+            let result = parser.parse(*byte);
+            if status >= 0xF0 && i >= data_length {
+                // No running status for system common messages.
+                assert!(matches!(result, Err(ParserError::InvalidStatus)));
+            } else if (i + 1) % data_length == 0 {
+                let mut message = vec![status];
+                message.extend_from_slice(&bytes[i + 1 - data_length..=i]);
+                assert_eq!(result.unwrap(), Some(message.as_slice()));
+            } else {
+                assert_eq!(result.unwrap(), None);
+            }
+        }
+    }
+}
+
+/// Warning when a SysEx message reaches half of the buffer.
+#[test]
+fn sysex_warning() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static WARNED_LENGTH: AtomicUsize = AtomicUsize::new(0);
+    static WARNINGS: AtomicUsize = AtomicUsize::new(0);
+
+    fn warn(length: usize) {
+        WARNED_LENGTH.store(length, Ordering::Relaxed);
+        WARNINGS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let mut parser = MidiStreamParser::<8>::with_config(ParserConfig {
+        sysex_warn_threshold: 4,
+        ..Default::default()
+    });
+    parser.set_sysex_warning(Some(warn));
+
+    for byte in [0xF0, 1, 2, 0xF7] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(WARNINGS.load(Ordering::Relaxed), 1);
+    assert_eq!(WARNED_LENGTH.load(Ordering::Relaxed), 4);
+
+    for byte in [0xF0, 1, 2] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(WARNINGS.load(Ordering::Relaxed), 1);
+
+    for byte in [3, 4, 5, 0xF7] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(WARNINGS.load(Ordering::Relaxed), 2);
+
+    for byte in [0xF0, 1, 0xF7] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(WARNINGS.load(Ordering::Relaxed), 2);
+}
+
+/// SysEx warning once per message when draining in ring buffer mode and reassembling.
+#[test]
+fn sysex_warning_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static WARNINGS: AtomicUsize = AtomicUsize::new(0);
+
+    fn warn(_length: usize) {
+        WARNINGS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let mut parser = MidiStreamParser::<8>::with_config(ParserConfig {
+        sysex_warn_threshold: 4,
+        sysex_ring_buffer: true,
+        ..Default::default()
+    });
+    parser.set_sysex_warning(Some(warn));
+
+    for byte in [0xF0, 1, 2, 3] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(WARNINGS.load(Ordering::Relaxed), 1);
+    assert_eq!(parser.take_sysex_chunk(&mut [0; 4]), 4);
+
+    for byte in [4, 5, 6, 7, 0xF7] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(WARNINGS.load(Ordering::Relaxed), 1);
+
+    // Length jumping past the threshold when continuing a fragment.
+    let mut parser = MidiStreamParser::<16>::with_config(ParserConfig {
+        sysex_warn_threshold: 4,
+        ..Default::default()
+    });
+    parser.set_sysex_warning(Some(warn));
+    parser.set_sysex_continuation(Some(|_| true));
+
+    for byte in [0xF0, 0x41, 1, 2, 3] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(WARNINGS.load(Ordering::Relaxed), 2);
+
+    for byte in [0xF0, 0x41] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(WARNINGS.load(Ordering::Relaxed), 3);
+
+    for byte in [4, 0xF7] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(WARNINGS.load(Ordering::Relaxed), 3);
+}
+
+/// Message read again after it was returned.
+#[test]
+fn last_message() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    assert_eq!(parser.last_message(), None);
+
+    for byte in [0x90, 60, 100] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(parser.last_message(), Some([0x90, 60, 100].as_ref()));
+
+    parser.parse(61).unwrap();
+    assert_eq!(parser.last_message(), Some([0x90, 60, 100].as_ref()));
+
+    for byte in [0xF0, 0x10, 0xF7] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(parser.last_message(), Some([0xF0, 0x10, 0xF7].as_ref()));
+
+    parser.parse(0xF0).unwrap();
+    assert_eq!(parser.last_message(), None);
+
+    parser.parse(0xF8).unwrap();
+    assert_eq!(parser.last_message(), Some([0xF8].as_ref()));
+
+    parser.reset();
+    assert_eq!(parser.last_message(), None);
+}
+
+/// Universal SysEx messages filtered by device ID.
+#[test]
+fn sysex_device_id() {
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        sysex_device_id: Some(0),
+        ..Default::default()
+    });
+
+    let sysex: [&[u8]; 4] = [
+        &[0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7],
+        &[0xF0, 0x7E, 0x05, 0x06, 0x01, 0xF7],
+        &[0xF0, 0x7F, 0x00, 0x04, 0x01, 0x00, 0x40, 0xF7],
+        &[0xF0, 0x41, 0x10, 0x42, 0xF7],
+    ];
+    let accepted = [true, false, true, true];
+
+    for (bytes, accepted) in sysex.iter().zip(accepted.iter()) {
+        let (last, start) = bytes.split_last().unwrap();
+        for byte in start {
+            assert_eq!(parser.parse(*byte).unwrap(), None);
+        }
+        let result = parser.parse(*last).unwrap();
+        assert_eq!(result, if *accepted { Some(*bytes) } else { None });
+    }
+}
+
+/// Number of messages in a buffer with SysEx and running status.
+#[test]
+fn count_messages() {
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        spec_compliant: true,
+        ..Default::default()
+    });
+
+    let bytes = [
+        0x90, 60, 100, 62, 100, 0xF0, 0x41, 0x10, 0xF8, 0xF7, 0xC0, 5, 6, 0xB0, 7,
+    ];
+    assert_eq!(parser.count_messages(&bytes), Ok(6));
+
+    // Message completed by the remaining data byte.
+    assert_eq!(parser.count_messages(&[90]), Ok(1));
+
+    assert!(matches!(
+        parser.count_messages(&[0xF3, 1, 2]),
+        Err(ParserError::InvalidStatus)
+    ));
+}
+
+/// Overflowing SysEx message interrupted by a new SysEx message.
+#[test]
+fn sysex_overflow_restart() {
+    let mut parser = MidiStreamParser::<4>::new();
+
+    for byte in [0xF0, 1, 2, 3] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    assert!(matches!(parser.parse(4), Err(ParserError::SysexOverflow)));
+
+    for byte in [0xF0, 5] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    assert_eq!(parser.parse(0xF7).unwrap(), Some([0xF0, 5, 0xF7].as_ref()));
+
+    let mut parser = MidiStreamParser::<4>::with_config(ParserConfig {
+        truncate_sysex_on_overflow: true,
+        ..Default::default()
+    });
+
+    for byte in [0xF0, 1, 2, 3] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    assert_eq!(parser.parse(4).unwrap(), Some([0xF0, 1, 2, 0xF7].as_ref()));
+    assert_eq!(parser.parse(5).unwrap(), None);
+
+    for byte in [0xF0, 6] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+    assert_eq!(parser.parse(0xF7).unwrap(), Some([0xF0, 6, 0xF7].as_ref()));
+    assert!(!parser.last_sysex_truncated());
+}
+
+/// SysEx message in progress persisted and completed by a fresh parser.
+#[test]
+fn serialize_sysex_state() {
+    let mut parser = MidiStreamParser::<16>::new();
+
+    for byte in [0xF0, 0x41, 0x10, 0x42] {
+        assert_eq!(parser.parse(byte).unwrap(), None);
+    }
+
+    let mut state = [0; 32];
+    assert_eq!(parser.serialize_sysex_state(&mut state[..8]), 0);
+    let length = parser.serialize_sysex_state(&mut state);
+    assert_eq!(length, 9);
+    assert_eq!(state[..length], [1, 4, 0, 0, 0, 0xF0, 0x41, 0x10, 0x42]);
+
+    let mut parser = MidiStreamParser::<16>::new();
+    parser.load_sysex_state(&state[..length]).unwrap();
+
+    assert_eq!(parser.parse(0x12).unwrap(), None);
+    assert_eq!(
+        parser.parse(0xF7).unwrap(),
+        Some([0xF0, 0x41, 0x10, 0x42, 0x12, 0xF7].as_ref())
+    );
+
+    let mut parser = MidiStreamParser::<3>::new();
+    assert!(matches!(
+        parser.load_sysex_state(&state[..length]),
+        Err(ParserError::InvalidLength)
+    ));
+    assert!(matches!(
+        parser.load_sysex_state(&state[..3]),
+        Err(ParserError::InvalidLength)
+    ));
+}
+
+/// SysEx message interrupted by a channel status byte.
+#[test]
+fn sysex_interrupted() {
+    let bytes = [0xF0, 0x41, 0x90, 60, 100, 0xF7];
+    let messages = [None, None, None, None, Some([0x90, 60, 100].as_ref()), None];
+
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        spec_compliant: true,
+        ..Default::default()
+    });
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        strict: true,
+        spec_compliant: true,
+        ..Default::default()
+    });
+
+    for (i, (byte, message)) in bytes.iter().zip(messages.iter()).enumerate() {
+        let result = parser.parse(*byte);
+        if i == 2 {
+            assert!(matches!(result, Err(ParserError::SysExDataCorrupt)));
+        } else {
+            assert_eq!(result.unwrap(), *message);
+        }
+    }
+}
+
+/// Parser seeded with running status completing a message from data bytes only.
+#[test]
+fn with_running_status() {
+    let mut parser = MidiStreamParser::<256>::with_running_status(0x91).unwrap();
+
+    assert_eq!(parser.parse(60).unwrap(), None);
+    assert_eq!(parser.parse(100).unwrap(), Some([0x91, 60, 100].as_ref()));
+    assert!(parser.last_used_running_status());
+
+    let mut parser = MidiStreamParser::<256>::with_running_status(0xD0).unwrap();
+
+    assert_eq!(parser.parse(64).unwrap(), Some([0xD0, 64].as_ref()));
+
+    for status in [0x7F, 0xF0, 0xF2, 0xF8] {
+        assert!(matches!(
+            MidiStreamParser::<256>::with_running_status(status),
+            Err(ParserError::InvalidStatus)
+        ));
+    }
+}
+
+/// Message lengths of restored running status.
+#[test]
+fn restore_message_length() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    for byte in [0xC0, 5, 0xB0, 7] {
+        parser.parse(byte).unwrap();
+    }
+    let state = parser.snapshot();
+
+    let mut parser = MidiStreamParser::<256>::new();
+    for byte in [0xE0, 0] {
+        parser.parse(byte).unwrap();
+    }
+    parser.restore(&state);
+
+    assert_eq!(parser.parse(90).unwrap(), Some([0xB0, 7, 90].as_ref()));
+    assert_eq!(parser.parse(10).unwrap(), None);
+    assert_eq!(parser.parse(20).unwrap(), Some([0xB0, 10, 20].as_ref()));
+}
+
+/// Message in progress at various points of a stream.
+#[test]
+fn mid_message() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    let bytes = [
+        0x90, 60, 100, 61, 0, 0xF8, 0xF2, 1, 2, 0xF0, 0x41, 0xF7, 0xC0, 5,
+    ];
+    let mid_messages = [
+        true, true, false, true, false, false, true, true, false, true, true, false, true, false,
+    ];
+
+    assert!(!parser.mid_message());
+
+    for (byte, mid_message) in bytes.iter().zip(mid_messages.iter()) {
+        parser.parse(*byte).unwrap();
+        assert_eq!(parser.mid_message(), *mid_message);
+    }
+}
+
+/// Batch APIs with empty input.
+#[test]
+fn empty_input() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    assert_eq!(parser.count_messages(&[]), Ok(0));
+    assert!(parser.is_idle());
+
+    for byte in [0x90, 60] {
+        parser.parse(byte).unwrap();
+    }
+
+    assert_eq!(parser.count_messages(&[]), Ok(0));
+    assert_eq!(parser.pending_len(), 2);
+    assert!(parser.mid_message());
+    assert_eq!(parser.parse(100).unwrap(), Some([0x90, 60, 100].as_ref()));
+
+    assert_eq!(message_spans(&[]).next(), None);
+}
+
+/// Running status after SysEx, cancelled by default and optionally preserved.
+#[test]
+fn preserve_running_status_across_sysex() {
+    let bytes = [0x90, 60, 100, 0xF0, 0x10, 0xF7, 61, 40];
+
+    let mut parser = MidiStreamParser::<256>::new();
+
+    for byte in &bytes[..6] {
+        parser.parse(*byte).unwrap();
+    }
+    assert!(matches!(parser.parse(61), Err(ParserError::InvalidStatus)));
+
+    let mut parser = MidiStreamParser::<256>::with_config(ParserConfig {
+        preserve_running_status_across_sysex: true,
+        spec_compliant: true,
+        ..Default::default()
+    });
+
+    let messages = [
+        None,
+        None,
+        Some([0x90, 60, 100].as_ref()),
+        None,
+        None,
+        Some([0xF0, 0x10, 0xF7].as_ref()),
+        None,
+        Some([0x90, 61, 40].as_ref()),
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        let result = parser.parse(*byte).unwrap();
+        assert_eq!(result, *message);
+    }
+    assert!(parser.last_used_running_status());
+
+    // Cancelled by a status byte interrupting SysEx.
+    for byte in [0xF0, 0x10, 0xF6, 0xF0, 0x20, 0xF7] {
+        parser.parse(byte).unwrap();
+    }
+    assert!(matches!(parser.parse(62), Err(ParserError::InvalidStatus)));
+}
+
+/// SysEx message aborted while keeping running status.
+#[test]
+fn clear_sysex() {
+    let mut parser = MidiStreamParser::<256>::new();
+
+    for byte in [0x90, 60, 100, 0xF0, 0x41, 0x10] {
+        parser.parse(byte).unwrap();
+    }
+    parser.clear_sysex();
+
+    assert!(!parser.mid_message());
+    assert_eq!(parser.parse(61).unwrap(), None);
+    assert_eq!(parser.parse(40).unwrap(), Some([0x90, 61, 40].as_ref()));
+
+    // Nothing left of the aborted message.
+    assert_eq!(parser.parse(0xF7).unwrap(), None);
+
+    parser.clear_sysex();
+    assert_eq!(parser.parse(62).unwrap(), None);
+}
+
+/// Parsers of different sizes used through the trait.
+#[test]
+fn dyn_parse() {
+    let mut small = MidiStreamParser::<4>::new();
+    let mut large = MidiStreamParser::<256>::new();
+    let mut parsers: [&mut dyn MidiParse; 2] = [&mut small, &mut large];
+
+    for parser in parsers.iter_mut() {
+        let mut messages = Vec::new();
+        for byte in [0xF0, 0x41, 0xF7, 0xC0, 5] {
+            if let Some(message) = parser.parse(byte).unwrap() {
+                messages.push(message.to_vec());
+            }
+        }
+        assert_eq!(messages, [vec![0xF0, 0x41, 0xF7], vec![0xC0, 5]]);
+
+        parser.parse(0x90).unwrap();
+        parser.reset();
+        assert!(matches!(parser.parse(60), Err(ParserError::InvalidStatus)));
+    }
+}
+
+/// End of SysEx inside a channel message ignored in lenient mode and truncating it in
+/// strict mode.
+#[test]
+fn stray_sysex_end_in_channel_message() {
+    let bytes = [0x90, 60, 0xF7, 127];
+
+    for strict in [false, true] {
+        let mut parser = MidiStreamParser::<16>::with_config(ParserConfig {
+            strict,
+            ..Default::default()
+        });
+
+        assert_eq!(parser.parse(bytes[0]).unwrap(), None);
+        assert_eq!(parser.parse(bytes[1]).unwrap(), None);
+        if strict {
+            assert!(matches!(
+                parser.parse(bytes[2]),
+                Err(ParserError::TruncatedMessage)
+            ));
+            assert!(matches!(
+                parser.parse(bytes[3]),
+                Err(ParserError::InvalidStatus)
+            ));
+        } else {
+            assert_eq!(parser.parse(bytes[2]).unwrap(), None);
+            assert_eq!(
+                parser.parse(bytes[3]).unwrap(),
+                Some([0x90, 60, 127].as_ref())
+            );
+        }
+    }
+}
+
+/// Song position pointer interrupted by start and end of SysEx in strict mode.
+#[test]
+fn song_position_truncated_by_sysex() {
+    let mut parser = MidiStreamParser::<16>::with_config(ParserConfig {
+        emit_sysex_start: true,
+        ..ParserConfig::strict()
+    });
+
+    assert_eq!(parser.parse(0xF2).unwrap(), None);
+    assert_eq!(parser.parse(0x10).unwrap(), None);
+    assert!(matches!(
+        parser.parse(0xF0),
+        Err(ParserError::TruncatedMessage)
+    ));
+    assert_eq!(parser.parse(0x41).unwrap(), None);
+    assert_eq!(
+        parser.parse(0xF7).unwrap(),
+        Some([0xF0, 0x41, 0xF7].as_ref())
+    );
+
+    assert_eq!(parser.parse(0xF2).unwrap(), None);
+    assert_eq!(parser.parse(0x10).unwrap(), None);
+    assert!(matches!(
+        parser.parse(0xF7),
+        Err(ParserError::TruncatedMessage)
+    ));
+    assert!(matches!(
+        parser.parse(0x20),
+        Err(ParserError::InvalidStatus)
+    ));
+}
+
+/// Echoed bytes equal to the input in both orders.
+#[test]
+fn parse_and_echo() {
+    let bytes = [0x90, 60, 100, 0xF8, 0x10, 0xF0, 0x41, 0xF7];
+
+    for echo_after_parse in [false, true] {
+        let mut parser = MidiStreamParser::<16>::with_config(ParserConfig {
+            echo_after_parse,
+            ..Default::default()
+        });
+        let mut echoed = Vec::new();
+        let mut messages = 0;
+
+        for byte in bytes {
+            let echo_len = echoed.len();
+            let result = parser.parse_and_echo(byte, &mut |byte| echoed.push(byte));
+            if result.unwrap().is_some() {
+                messages += 1;
+            }
+            assert_eq!(echoed.len(), echo_len + 1);
+        }
+
+        assert_eq!(echoed, bytes);
+        assert_eq!(messages, 3);
+    }
+}
+
+/// Incomplete message discarded after a long gap between status and data bytes.
+#[test]
+fn stale_message_timeout() {
+    let mut parser = MidiStreamParser::<16>::with_config(ParserConfig {
+        stale_message_timeout: Some(100),
+        ..Default::default()
+    });
+
+    assert_eq!(parser.parse_timed(0x90, 0).unwrap(), None);
+    assert_eq!(parser.parse_timed(60, 50).unwrap(), None);
+    assert_eq!(
+        parser.parse_timed(0xF8, 140).unwrap(),
+        Some([0xF8].as_ref())
+    );
+    assert!(matches!(
+        parser.parse_timed(100, 200),
+        Err(ParserError::InvalidStatus)
+    ));
+
+    // Gaps within the timeout and running status kept after a complete message.
+    let bytes = [0xB0, 7, 90, 8, 60];
+    let timestamps = [300, 400, 500, 1000, 1100];
+    let messages = [None, None, Some([0xB0, 7, 90]), None, Some([0xB0, 8, 60])];
+
+    for ((byte, timestamp), message) in bytes.iter().zip(timestamps).zip(messages) {
+        let result = parser.parse_timed(*byte, timestamp).unwrap();
+        assert_eq!(result, message.as_ref().map(|message| message.as_ref()));
+    }
+}
+
+/// Running status run interrupted by a control change on a different channel.
+#[test]
+fn running_status_hook() {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    // Events packed as bytes, 0x7F for broken and the status byte for started.
+    static EVENTS: AtomicU64 = AtomicU64::new(0);
+
+    fn record(event: RunningStatus) {
+        let byte = match event {
+            RunningStatus::Started(status) => status,
+            RunningStatus::Broken => 0x7F,
+        };
+        let events = EVENTS.load(Ordering::Relaxed);
+        EVENTS.store(events << 8 | byte as u64, Ordering::Relaxed);
+    }
+
+    let mut parser = MidiStreamParser::<16>::new();
+    parser.set_running_status_hook(Some(record));
+
+    for byte in [0x90, 60, 100, 62, 100, 0x90, 64, 100, 0xF8, 0xB1, 7, 90, 8] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(EVENTS.load(Ordering::Relaxed), 0x90_7F_B1);
+
+    EVENTS.store(0, Ordering::Relaxed);
+    for byte in [60, 0xF6, 0xF8] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(EVENTS.load(Ordering::Relaxed), 0x7F);
+}
+
+/// Parser and configuration constructed in const context.
+#[test]
+fn const_new() {
+    const CONFIG: ParserConfig = ParserConfig::new();
+    const PARSER: MidiStreamParser<16> = MidiStreamParser::with_config(CONFIG);
+    static DEFAULT: MidiStreamParser<16> = MidiStreamParser::new();
+
+    assert_eq!(CONFIG, ParserConfig::default());
+    assert!(DEFAULT.is_idle());
+    assert_eq!(DEFAULT.pending_len(), 0);
+
+    let mut parser = PARSER;
+    assert_eq!(parser.parse(0xC0).unwrap(), None);
+    assert_eq!(parser.parse(5).unwrap(), Some([0xC0, 5].as_ref()));
+}
+
+/// Trace of the decisions for a short stream.
+#[cfg(feature = "trace")]
+#[test]
+fn trace_log() {
+    let mut parser = MidiStreamParser::<16>::new();
+
+    for byte in [0x10, 0x90, 60, 100, 0xF8] {
+        parser.parse(byte).ok();
+    }
+    parser.reset();
+
+    let trace: Vec<_> = parser
+        .trace_log()
+        .map(|entry| (entry.byte, entry.decision))
+        .collect();
+    assert_eq!(
+        trace,
+        [
+            (0x10, TraceDecision::Error(ParserError::InvalidStatus)),
+            (0x90, TraceDecision::Pending),
+            (60, TraceDecision::Pending),
+            (100, TraceDecision::Emitted(3)),
+            (0xF8, TraceDecision::Emitted(1)),
+        ]
+    );
+}
+
+/// Control changes and SysEx dropped and channels remapped by a byte filter.
+#[test]
+fn parse_with_filter() {
+    let bytes = [
+        0x90, 60, 100, 0xB0, 7, 90, 8, 0xF8, 60, 0x90, 62, 100, 64, 0, 0xF0, 0x41, 0xF7, 0xC0, 5,
+    ];
+    let expected = [
+        [0x91, 60, 100].as_ref(),
+        [0xF8].as_ref(),
+        [0x91, 62, 100].as_ref(),
+        [0x91, 64, 0].as_ref(),
+        [0xC1, 5].as_ref(),
+    ];
+
+    let mut parser = MidiStreamParser::<16>::new();
+    let mut messages = Vec::new();
+
+    for byte in bytes {
+        let result = parser.parse_with_filter(byte, |byte| match byte {
+            0xB0..=0xBF | 0xF0 => FilterAction::Drop,
+            0x80..=0xEF => FilterAction::Replace(byte | 0x01),
+            _ => FilterAction::Pass,
+        });
+        if let Some(message) = result.unwrap() {
+            messages.push(message.to_vec());
+        }
+    }
+
+    assert_eq!(messages, expected);
+}
+
+/// Parser equal to its clone and different after more bytes.
+#[test]
+fn parser_eq() {
+    let mut parser = MidiStreamParser::<16>::new();
+    for byte in [0xF0, 1, 2, 3, 4, 0xF7, 0x90, 60, 100, 0xF0, 0x41, 0x10] {
+        parser.parse(byte).unwrap();
+    }
+
+    let mut clone = parser.clone();
+    assert_eq!(clone, parser);
+
+    clone.parse(0x20).unwrap();
+    assert_ne!(clone, parser);
+
+    // Same state reached through a different message with unused buffer bytes differing.
+    let mut other = MidiStreamParser::<16>::new();
+    for byte in [0xF0, 5, 6, 7, 8, 0xF7, 0x90, 60, 100, 0xF0, 0x41, 0x10] {
+        other.parse(byte).unwrap();
+    }
+    assert_ne!(other.sysex_message[3], parser.sysex_message[3]);
+    assert_eq!(other, parser);
+
+    let state = parser.snapshot();
+    parser.parse(0xF7).unwrap();
+    assert_ne!(other, parser);
+    parser.restore(&state);
+    assert_eq!(other, parser);
+}
+
+/// Event carrying the decoded message and the timestamp of its completing byte.
+#[test]
+fn parse_event() {
+    let mut parser = MidiStreamParser::<16>::new();
+
+    let bytes = [0xB2, 7, 0xF8, 90];
+    let mut events = Vec::new();
+
+    for (timestamp, byte) in bytes.iter().enumerate() {
+        if let Some(event) = parser
+            .parse_event(*byte, Some(timestamp as u64 * 10))
+            .unwrap()
+        {
+            events.push(MidiEvent {
+                timestamp: event.timestamp,
+                message: event.message.to_static().unwrap(),
+            });
+        }
+    }
+
+    assert_eq!(
+        events,
+        [
+            MidiEvent {
+                timestamp: Some(20),
+                message: MidiMessage::SystemRealtime(SystemRealtimeMessage::TimingClock)
+            },
+            MidiEvent {
+                timestamp: Some(30),
+                message: MidiMessage::ControlChange {
+                    channel: 2,
+                    controller: 7,
+                    value: 90
+                }
+            },
+        ]
+    );
+
+    assert_eq!(
+        parser
+            .parse_event(0xFE, None)
+            .unwrap()
+            .map(|event| event.timestamp),
+        Some(None)
+    );
+}
+
+/// Interrupted SysEx followed by a clean note on, with and without reset on error.
+#[test]
+fn reset_on_error() {
+    let bytes = [0xF0, 1, 0x90, 60, 61, 0x90, 62, 100];
+
+    for reset_on_error in [false, true] {
+        let mut parser = MidiStreamParser::<16>::with_config(ParserConfig {
+            strict: true,
+            spec_compliant: true,
+            reset_on_error,
+            ..Default::default()
+        });
+        let mut messages = Vec::new();
+        let mut errors = 0;
+
+        for byte in bytes {
+            match parser.parse(byte) {
+                Ok(Some(message)) => messages.push(message.to_vec()),
+                Ok(None) => {}
+                Err(_) => errors += 1,
+            }
+        }
+
+        if reset_on_error {
+            // Status byte discarded, the data bytes are rejected.
+            assert_eq!(errors, 3);
+            assert_eq!(messages, [vec![0x90, 62, 100]]);
+        } else {
+            // Data byte completing the corrupted message.
+            assert_eq!(errors, 1);
+            assert_eq!(messages, [vec![0x90, 60, 61], vec![0x90, 62, 100]]);
+        }
+    }
+}
+
+/// Undefined realtime byte inside a note on with reset on error.
+#[test]
+fn reset_on_error_realtime() {
+    let mut parser = MidiStreamParser::<16>::with_config(ParserConfig {
+        reset_on_error: true,
+        ..ParserConfig::strict()
+    });
+
+    assert_eq!(parser.parse(0x90), Ok(None));
+    assert_eq!(parser.parse(60), Ok(None));
+    assert!(matches!(
+        parser.parse(0xF9),
+        Err(ParserError::UndefinedStatus(0xF9))
+    ));
+    assert_eq!(parser.parse(127), Ok(Some([0x90, 60, 127].as_ref())));
+}
+
+/// Buffers ending in the middle of messages, fed again with the remaining bytes.
+#[test]
+fn parse_dma() {
+    let mut parser = MidiStreamParser::<16>::new();
+    let mut messages = Vec::new();
+
+    let buf = [0x90, 60, 100, 0xF8, 0xB1, 7, 0xFE];
+    let consumed = parser.parse_dma(&buf, |message| messages.push(message.to_vec()));
+    assert_eq!(consumed, 4);
+    assert_eq!(messages, [vec![0x90, 60, 100], vec![0xF8], vec![0xFE]]);
+
+    // Realtime message of the remaining bytes not passed again.
+    messages.clear();
+    let buf = [0xB1, 7, 0xFE, 90, 8, 0xF0, 0x41];
+    let consumed = parser.parse_dma(&buf, |message| messages.push(message.to_vec()));
+    assert_eq!(consumed, 7);
+    assert_eq!(messages, [vec![0xB1, 7, 90]]);
+
+    // Remaining status byte discarded from the parsing state.
+    messages.clear();
+    let buf = [0xF7, 0xC0];
+    let consumed = parser.parse_dma(&buf, |message| messages.push(message.to_vec()));
+    assert_eq!(consumed, 1);
+    assert_eq!(messages, [vec![0xF0, 0x41, 0xF7]]);
+    assert!(matches!(parser.parse(5), Err(ParserError::InvalidStatus)));
+
+    // Running status kept for a remaining data byte.
+    let consumed = parser.parse_dma(&[0x90, 60, 100, 61], |_| {});
+    assert_eq!(consumed, 3);
+    assert_eq!(parser.parse(61).unwrap(), None);
+    assert_eq!(parser.parse(40).unwrap(), Some([0x90, 61, 40].as_ref()));
+}
+
+/// Timing clocks inside a SysEx message spanning DMA buffers and a retained fragment.
+#[test]
+fn parse_dma_sysex() {
+    let mut parser = MidiStreamParser::<16>::with_config(ParserConfig {
+        spec_compliant: true,
+        ..Default::default()
+    });
+    let mut messages = Vec::new();
+
+    let buf = [0xF0, 0x41, 1, 0xF8, 2, 0xF8];
+    let consumed = parser.parse_dma(&buf, |message| messages.push(message.to_vec()));
+    assert_eq!(consumed, 6);
+    assert_eq!(messages, [vec![0xF8], vec![0xF8]]);
+
+    messages.clear();
+    let buf = [3, 0xF8, 4, 0xF7, 0x90, 60];
+    let consumed = parser.parse_dma(&buf, |message| messages.push(message.to_vec()));
+    assert_eq!(consumed, 4);
+    assert_eq!(messages, [vec![0xF8], vec![0xF0, 0x41, 1, 2, 3, 4, 0xF7]]);
+
+    // SysEx message longer than the DMA buffer.
+    messages.clear();
+    let buf = [0x90, 60, 100, 0xF0, 0x41];
+    let consumed = parser.parse_dma(&buf, |message| messages.push(message.to_vec()));
+    assert_eq!(consumed, 5);
+    for buf in [[5, 6], [7, 8]] {
+        assert_eq!(parser.parse_dma(&buf, |_| {}), 2);
+    }
+    let consumed = parser.parse_dma(&[0xF7], |message| messages.push(message.to_vec()));
+    assert_eq!(consumed, 1);
+    assert_eq!(
+        messages,
+        [vec![0x90, 60, 100], vec![0xF0, 0x41, 5, 6, 7, 8, 0xF7]]
+    );
+
+    // SysEx message interrupted by an incomplete message fed again.
+    for byte in [0xF0, 0x41, 1] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(parser.parse_dma(&[2, 0x90, 60], |_| {}), 1);
+    messages.clear();
+    let consumed = parser.parse_dma(&[0x90, 60, 100], |message| messages.push(message.to_vec()));
+    assert_eq!(consumed, 3);
+    assert_eq!(messages, [vec![0x90, 60, 100]]);
+
+    // Fragment kept when the continuing message is incomplete.
+    parser.set_sysex_continuation(Some(|_| true));
+    for byte in [0xF0, 0x41, 1, 2] {
+        parser.parse(byte).unwrap();
+    }
+    parser.reset();
+
+    assert_eq!(parser.parse_dma(&[0xF0, 0x41, 3], |_| {}), 3);
+
+    messages.clear();
+    let consumed = parser.parse_dma(&[0xF7], |message| messages.push(message.to_vec()));
+    assert_eq!(consumed, 1);
+    assert_eq!(messages, [vec![0xF0, 0x41, 1, 2, 3, 0xF7]]);
+}
+
+/// Backward compatible behavior of system common messages and SysEx by default.
+#[test]
+fn default_not_spec_compliant() {
+    let mut parser = MidiStreamParser::<16>::new();
+
+    // System common messages keeping running status.
+    let bytes = [0xF1, 0x20, 0x30, 0xF2, 1, 2, 3, 4, 0xF3, 5, 6];
+    let messages = [
+        None,
+        Some([0xF1, 0x20].as_ref()),
+        Some([0xF1, 0x30].as_ref()),
+        None,
+        None,
+        Some([0xF2, 1, 2].as_ref()),
+        None,
+        Some([0xF2, 3, 4].as_ref()),
+        None,
+        Some([0xF3, 5].as_ref()),
+        Some([0xF3, 6].as_ref()),
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        assert_eq!(parser.parse(*byte).unwrap(), *message);
+    }
+
+    // Tune request not returned, but cancelling running status.
+    assert_eq!(parser.parse(0xF6).unwrap(), None);
+    assert!(matches!(parser.parse(7), Err(ParserError::InvalidStatus)));
+
+    // Status bytes inside SysEx ignored.
+    let bytes = [0xF0, 0x41, 0x90, 1, 0xF6, 2, 0xF7];
+    for byte in &bytes[..6] {
+        assert_eq!(parser.parse(*byte).unwrap(), None);
+    }
+    assert_eq!(
+        parser.parse(0xF7).unwrap(),
+        Some([0xF0, 0x41, 1, 2, 0xF7].as_ref())
+    );
+
+    // Reported in strict mode, also without spec compliance.
+    let mut parser = MidiStreamParser::<16>::with_config(ParserConfig {
+        strict: true,
+        ..Default::default()
+    });
+    for byte in [0xF0, 0x41] {
+        parser.parse(byte).unwrap();
+    }
+    assert!(matches!(
+        parser.parse(0x90),
+        Err(ParserError::SysExDataCorrupt)
+    ));
+    assert_eq!(parser.parse(1).unwrap(), None);
+    assert_eq!(
+        parser.parse(0xF7).unwrap(),
+        Some([0xF0, 0x41, 1, 0xF7].as_ref())
+    );
+}
+
+/// Spec-faithful parsing with the strict preset.
+#[test]
+fn strict_preset() {
+    let config = ParserConfig::strict();
+    assert!(config.strict);
+    assert!(config.spec_compliant);
+    assert!(!config.preserve_running_status_across_sysex);
+
+    let mut parser = MidiStreamParser::<16>::with_config(config);
+
+    // Tune request cancelling running status.
+    for byte in [0x90, 60, 100] {
+        parser.parse(byte).unwrap();
+    }
+    assert_eq!(parser.parse(0xF6).unwrap(), Some([0xF6].as_ref()));
+    assert!(matches!(parser.parse(61), Err(ParserError::InvalidStatus)));
+
+    // SysEx interrupted by a status byte.
+    for byte in [0xF0, 0x41] {
+        parser.parse(byte).unwrap();
+    }
+    assert!(matches!(
+        parser.parse(0xC0),
+        Err(ParserError::SysExDataCorrupt)
+    ));
+    assert_eq!(parser.parse(5).unwrap(), Some([0xC0, 5].as_ref()));
+
+    // Stray end of SysEx ignored, undefined realtime and truncated messages reported.
+    assert_eq!(parser.parse(0xF7).unwrap(), None);
+    assert!(matches!(
+        parser.parse(0xF9),
+        Err(ParserError::UndefinedStatus(0xF9))
+    ));
+    parser.parse(0xF2).unwrap();
+    parser.parse(1).unwrap();
+    assert!(matches!(
+        parser.parse(0x90),
+        Err(ParserError::TruncatedMessage)
+    ));
+
+    // Tune request interrupting an incomplete message or SysEx.
+    parser.parse(60).unwrap();
+    assert!(matches!(
+        parser.parse(0xF6),
+        Err(ParserError::TruncatedMessage)
+    ));
+    for byte in [0xF0, 0x01] {
+        parser.parse(byte).unwrap();
+    }
+    assert!(matches!(
+        parser.parse(0xF6),
+        Err(ParserError::SysExDataCorrupt)
+    ));
+    assert!(matches!(parser.parse(1), Err(ParserError::InvalidStatus)));
+}
+
+/// Messages and errors of a whole buffer.
+#[test]
+fn parse_slice() {
+    let mut parser = MidiStreamParser::<16>::new();
+    let mut messages = Vec::new();
+
+    parser.parse_slice(
+        &[0x10, 0x90, 60, 0xF8, 100, 61, 40, 0xF0, 0x41, 0xF7, 0xC0],
+        |message| messages.push(message.map(|message| message.to_vec())),
+    );
+
+    assert_eq!(
+        messages,
+        [
+            Err(ParserError::InvalidStatus),
+            Ok(vec![0xF8]),
+            Ok(vec![0x90, 60, 100]),
+            Ok(vec![0x90, 61, 40]),
+            Ok(vec![0xF0, 0x41, 0xF7]),
+        ]
+    );
+
+    // Message started in the previous buffer.
+    messages.clear();
+    parser.parse_slice(&[5], |message| {
+        messages.push(message.map(|message| message.to_vec()))
+    });
+    assert_eq!(messages, [Ok(vec![0xC0, 5])]);
+}
+
+/// Nothing passed for an empty buffer.
+#[test]
+fn parse_slice_empty() {
+    let mut parser = MidiStreamParser::<16>::new();
+    let mut count = 0;
+
+    parser.parse_slice(&[], |_| count += 1);
+
+    assert_eq!(count, 0);
+    assert!(parser.is_idle());
+}
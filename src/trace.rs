@@ -0,0 +1,75 @@
+//! Trace buffer recording the last parse decisions, see [`MidiStreamParser::trace_log`](crate::MidiStreamParser::trace_log).
+
+use crate::ParserError;
+
+/// Number of entries kept in the trace buffer.
+pub const TRACE_LEN: usize = 32;
+
+/// Byte fed into the parser and the decision taken for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// Byte fed into the parser.
+    pub byte: u8,
+
+    /// Decision taken for the byte.
+    pub decision: TraceDecision,
+}
+
+/// Decision taken by the parser for a single byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceDecision {
+    /// Byte consumed without completing a message.
+    Pending,
+
+    /// Message of the given length completed and returned.
+    Emitted(usize),
+
+    /// Error returned.
+    Error(ParserError),
+}
+
+/// Ring buffer of trace entries.
+#[derive(Debug, Clone)]
+pub(crate) struct Trace {
+    /// Entries in the order of recording, starting at `start`.
+    entries: [TraceEntry; TRACE_LEN],
+
+    /// Index of the oldest entry.
+    start: usize,
+
+    /// Number of entries recorded.
+    length: usize,
+}
+
+impl Trace {
+    /// Returns an empty trace.
+    pub(crate) const fn new() -> Self {
+        Self {
+            entries: [TraceEntry {
+                byte: 0,
+                decision: TraceDecision::Pending,
+            }; TRACE_LEN],
+            start: 0,
+            length: 0,
+        }
+    }
+
+    /// Record an entry, replacing the oldest one when the buffer is full.
+    pub(crate) fn record(&mut self, entry: TraceEntry) {
+        if self.length < TRACE_LEN {
+            self.entries[(self.start + self.length) % TRACE_LEN] = entry;
+            self.length += 1;
+        } else {
+            self.entries[self.start] = entry;
+            self.start = (self.start + 1) % TRACE_LEN;
+        }
+    }
+
+    /// Returns an iterator over the entries, oldest first.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = TraceEntry> + '_ {
+        (0..self.length).map(move |index| self.entries[(self.start + index) % TRACE_LEN])
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,20 @@
+//! Unit tests
+
+use super::*;
+
+/// Oldest entries replaced once the buffer is full.
+#[test]
+fn wrap_around() {
+    let mut trace = Trace::new();
+
+    for byte in 0..TRACE_LEN as u8 + 3 {
+        trace.record(TraceEntry {
+            byte,
+            decision: TraceDecision::Pending,
+        });
+    }
+
+    let bytes: Vec<u8> = trace.iter().map(|entry| entry.byte).collect();
+    let expected: Vec<u8> = (3..TRACE_LEN as u8 + 3).collect();
+    assert_eq!(bytes, expected);
+}
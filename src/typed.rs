@@ -0,0 +1,61 @@
+//! Parser returning typed messages.
+
+use crate::{MidiMessage, MidiStreamParser, ParserConfig, ParserError};
+
+/// Parser returning completed messages decoded as [`MidiMessage`] instead of raw bytes,
+/// for code that never needs the bytes themselves.
+///
+/// Works like [`MidiStreamParser::parse_typed`], including the message filter, and gives
+/// access to the underlying parser for everything else.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypedParser<const SYSEX_MAX_LEN: usize> {
+    /// Parser constructing the messages.
+    parser: MidiStreamParser<SYSEX_MAX_LEN>,
+}
+
+impl<const SYSEX_MAX_LEN: usize> TypedParser<SYSEX_MAX_LEN> {
+    /// Returns a new parser, usable in const context.
+    pub const fn new() -> Self {
+        Self::with_config(ParserConfig::new())
+    }
+
+    /// Returns a new parser using the given configuration, usable in const context.
+    pub const fn with_config(config: ParserConfig) -> Self {
+        Self {
+            parser: MidiStreamParser::with_config(config),
+        }
+    }
+
+    /// Feed a byte into the parser and return a completed message.
+    #[must_use = "the completed message is lost if it is not used"]
+    pub fn parse(&mut self, byte: u8) -> Result<Option<MidiMessage<'_>>, ParserError> {
+        self.parser.parse_typed(byte)
+    }
+
+    /// Returns the underlying parser.
+    pub fn parser(&self) -> &MidiStreamParser<SYSEX_MAX_LEN> {
+        &self.parser
+    }
+
+    /// Returns the underlying parser for changing its state, e.g. to set a message filter.
+    pub fn parser_mut(&mut self) -> &mut MidiStreamParser<SYSEX_MAX_LEN> {
+        &mut self.parser
+    }
+
+    /// Returns the underlying parser, consuming the wrapper.
+    pub fn into_inner(self) -> MidiStreamParser<SYSEX_MAX_LEN> {
+        self.parser
+    }
+}
+
+impl<const SYSEX_MAX_LEN: usize> From<MidiStreamParser<SYSEX_MAX_LEN>>
+    for TypedParser<SYSEX_MAX_LEN>
+{
+    /// Wraps an existing parser, continuing with its state.
+    fn from(parser: MidiStreamParser<SYSEX_MAX_LEN>) -> Self {
+        Self { parser }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,64 @@
+//! Unit tests
+
+use super::*;
+use crate::{MessageFilter, SystemRealtimeMessage};
+
+/// Typed messages from a stream with running status, realtime and SysEx.
+#[test]
+fn parse_typed_messages() {
+    let mut parser = TypedParser::<16>::new();
+
+    let bytes = [0x90, 60, 100, 0xF8, 61, 0, 0xF0, 0x41, 0xF7];
+    let messages = [
+        None,
+        None,
+        Some(MidiMessage::NoteOn {
+            channel: 0,
+            note: 60,
+            velocity: 100,
+        }),
+        Some(MidiMessage::SystemRealtime(
+            SystemRealtimeMessage::TimingClock,
+        )),
+        None,
+        Some(MidiMessage::NoteOn {
+            channel: 0,
+            note: 61,
+            velocity: 0,
+        }),
+        None,
+        None,
+        Some(MidiMessage::SysEx(&[0xF0, 0x41, 0xF7])),
+    ];
+
+    for (byte, message) in bytes.iter().zip(messages.iter()) {
+        assert_eq!(parser.parse(*byte), Ok(*message));
+    }
+
+    assert!(!parser.parser().mid_message());
+}
+
+/// Message filter set through the underlying parser.
+#[test]
+fn filtered() {
+    let mut parser = TypedParser::<16>::from(MidiStreamParser::new());
+    parser.parser_mut().set_message_filter(Some(MessageFilter {
+        predicate: |message| matches!(message, MidiMessage::ProgramChange { .. }),
+        exempt_realtime: false,
+    }));
+
+    for byte in [0xB0, 7, 100, 0xC1] {
+        assert_eq!(parser.parse(byte), Ok(None));
+    }
+    assert_eq!(
+        parser.parse(5),
+        Ok(Some(MidiMessage::ProgramChange {
+            channel: 1,
+            program: 5
+        }))
+    );
+
+    // Running status kept by the underlying parser.
+    let mut parser = parser.into_inner();
+    assert_eq!(parser.parse(6), Ok(Some([0xC1, 6].as_ref())));
+}
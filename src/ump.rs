@@ -0,0 +1,64 @@
+//! Translation between MIDI 1.0 messages and Universal MIDI Packets (UMP).
+
+use crate::{EncodeError, MidiMessage, MidiStreamEncoder, ParserError};
+
+/// UMP message type for system common and realtime messages.
+const MESSAGE_TYPE_SYSTEM: u32 = 0x1;
+
+/// UMP message type for MIDI 1.0 channel voice messages.
+const MESSAGE_TYPE_CHANNEL_VOICE: u32 = 0x2;
+
+/// Translate a message into a 32-bit UMP for a group in the range 0-15.
+///
+/// Channel voice messages are translated into MIDI 1.0 channel voice packets, system
+/// common and realtime messages into system packets with the unused bytes set to 0.
+/// SysEx messages are not supported.
+pub fn to_ump(message: &MidiMessage, group: u8) -> Result<u32, EncodeError> {
+    if group > 0x0F {
+        return Err(EncodeError::DataOutOfRange);
+    }
+
+    let message_type = match message {
+        MidiMessage::SystemCommon(_) | MidiMessage::SystemRealtime(_) => MESSAGE_TYPE_SYSTEM,
+        _ if message.channel().is_some() => MESSAGE_TYPE_CHANNEL_VOICE,
+        _ => return Err(EncodeError::InvalidStatus),
+    };
+
+    let mut bytes = [0; 3];
+    MidiStreamEncoder::new().encode(message, &mut bytes)?;
+
+    Ok(message_type << 28
+        | (group as u32) << 24
+        | (bytes[0] as u32) << 16
+        | (bytes[1] as u32) << 8
+        | bytes[2] as u32)
+}
+
+/// Translate a 32-bit MIDI 1.0 channel voice UMP into a message.
+/// The group can be read with [`ump_group`].
+pub fn from_ump(packet: u32) -> Result<MidiMessage<'static>, ParserError> {
+    if packet >> 28 != MESSAGE_TYPE_CHANNEL_VOICE {
+        return Err(ParserError::InvalidMessageType);
+    }
+
+    let status = (packet >> 16) as u8;
+    let data1 = (packet >> 8) as u8;
+    let data2 = packet as u8;
+
+    if !(0x80..=0xEF).contains(&status) {
+        return Err(ParserError::InvalidStatus);
+    }
+    if data1 > 0x7F || data2 > 0x7F {
+        return Err(ParserError::InvalidData);
+    }
+
+    Ok(MidiMessage::from_channel_voice(status, data1, data2))
+}
+
+/// Returns the group in the range 0-15 of a UMP.
+pub fn ump_group(packet: u32) -> u8 {
+    (packet >> 24) as u8 & 0x0F
+}
+
+#[cfg(test)]
+mod tests;
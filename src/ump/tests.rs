@@ -0,0 +1,94 @@
+//! Unit tests
+
+use super::*;
+
+/// Channel voice messages translated to UMP and back.
+#[test]
+fn round_trip() {
+    let messages = [
+        MidiMessage::NoteOn {
+            channel: 3,
+            note: 60,
+            velocity: 100,
+        },
+        MidiMessage::ControlChange {
+            channel: 0,
+            controller: 7,
+            value: 127,
+        },
+        MidiMessage::ProgramChange {
+            channel: 15,
+            program: 5,
+        },
+        MidiMessage::PitchBend {
+            channel: 1,
+            value: 0x2ABC,
+        },
+    ];
+
+    for (group, message) in messages.iter().enumerate() {
+        let packet = to_ump(message, group as u8).unwrap();
+        assert_eq!(ump_group(packet), group as u8);
+        assert_eq!(from_ump(packet), Ok(*message));
+    }
+}
+
+/// Known packet values.
+#[test]
+fn packet_layout() {
+    let message = MidiMessage::NoteOn {
+        channel: 3,
+        note: 60,
+        velocity: 100,
+    };
+    assert_eq!(to_ump(&message, 5), Ok(0x2593_3C64));
+
+    let message = MidiMessage::ProgramChange {
+        channel: 0,
+        program: 5,
+    };
+    assert_eq!(to_ump(&message, 0), Ok(0x20C0_0500));
+}
+
+/// Invalid packets and messages.
+#[test]
+fn invalid() {
+    assert_eq!(from_ump(0x1090_3C64), Err(ParserError::InvalidMessageType));
+    assert_eq!(from_ump(0x4090_3C64), Err(ParserError::InvalidMessageType));
+    assert_eq!(from_ump(0x20F8_0000), Err(ParserError::InvalidStatus));
+    assert_eq!(from_ump(0x2090_3C80), Err(ParserError::InvalidData));
+
+    let message = MidiMessage::SysEx(&[0xF0, 0x41, 0xF7]);
+    assert_eq!(to_ump(&message, 0), Err(EncodeError::InvalidStatus));
+
+    let message = MidiMessage::NoteOn {
+        channel: 0,
+        note: 60,
+        velocity: 100,
+    };
+    assert_eq!(to_ump(&message, 16), Err(EncodeError::DataOutOfRange));
+}
+
+/// Known system packet values.
+#[test]
+fn system_packets() {
+    use crate::{SystemCommonMessage, SystemRealtimeMessage};
+
+    let message = MidiMessage::SystemRealtime(SystemRealtimeMessage::TimingClock);
+    assert_eq!(to_ump(&message, 0), Ok(0x10F8_0000));
+
+    let message = MidiMessage::SystemRealtime(SystemRealtimeMessage::Start);
+    assert_eq!(to_ump(&message, 3), Ok(0x13FA_0000));
+
+    let message = MidiMessage::SystemCommon(SystemCommonMessage::SongPosition(0x110));
+    assert_eq!(to_ump(&message, 5), Ok(0x15F2_1002));
+
+    let message = MidiMessage::SystemCommon(SystemCommonMessage::QuarterFrame {
+        message_type: 7,
+        value: 3,
+    });
+    assert_eq!(to_ump(&message, 0), Ok(0x10F1_7300));
+
+    let message = MidiMessage::SystemCommon(SystemCommonMessage::SongSelect(128));
+    assert_eq!(to_ump(&message, 0), Err(EncodeError::DataOutOfRange));
+}
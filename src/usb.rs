@@ -0,0 +1,86 @@
+//! Parsing of USB-MIDI event packets with a separate parser per virtual cable.
+
+use crate::{MidiStreamParser, ParserConfig, ParserError};
+
+/// Parser for USB-MIDI 1.0 event packets, keeping a separate parser and SysEx buffer for
+/// each of the first `CABLES` virtual cables, so SysEx messages interleaved from
+/// different cables don't corrupt each other.
+///
+/// A packet consists of the cable number in the high nibble and the code index number
+/// (CIN) in the low nibble of the first byte, followed by three MIDI bytes of which the
+/// CIN determines the number in use.
+#[derive(Debug, Clone)]
+pub struct UsbMidiParser<const CABLES: usize, const SYSEX_MAX_LEN: usize> {
+    /// Parser for each cable.
+    parsers: [MidiStreamParser<SYSEX_MAX_LEN>; CABLES],
+}
+
+impl<const CABLES: usize, const SYSEX_MAX_LEN: usize> Default
+    for UsbMidiParser<CABLES, SYSEX_MAX_LEN>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CABLES: usize, const SYSEX_MAX_LEN: usize> UsbMidiParser<CABLES, SYSEX_MAX_LEN> {
+    /// Parser of a single cable in its initial state. Follows the specification, as each
+    /// packet carries complete messages including system common ones.
+    const PARSER: MidiStreamParser<SYSEX_MAX_LEN> = MidiStreamParser::with_config(ParserConfig {
+        spec_compliant: true,
+        ..ParserConfig::new()
+    });
+
+    /// Returns a new parser.
+    pub const fn new() -> Self {
+        Self {
+            parsers: [Self::PARSER; CABLES],
+        }
+    }
+
+    /// Feed an event packet into the parser of its cable and return the cable number
+    /// and the completed message.
+    ///
+    /// Packets of cables without parser and with reserved code index numbers 0 and 1
+    /// are ignored.
+    pub fn parse_packet(&mut self, packet: [u8; 4]) -> Result<Option<(u8, &[u8])>, ParserError> {
+        let cable = packet[0] >> 4;
+        let length = match packet[0] & 0x0F {
+            0x5 | 0xF => 1,
+            0x2 | 0x6 | 0xC | 0xD => 2,
+            0x3 | 0x4 | 0x7..=0xB | 0xE => 3,
+            _ => return Ok(None),
+        };
+
+        let parser = match self.parsers.get_mut(cable as usize) {
+            Some(parser) => parser,
+            None => return Ok(None),
+        };
+
+        let mut complete = false;
+        for byte in &packet[1..=length] {
+            complete = parser.parse(*byte)?.is_some();
+        }
+
+        if complete {
+            Ok(parser.last_message().map(|message| (cable, message)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the parser of a cable.
+    pub fn parser(&self, cable: u8) -> Option<&MidiStreamParser<SYSEX_MAX_LEN>> {
+        self.parsers.get(cable as usize)
+    }
+
+    /// Reset the parsers of all cables.
+    pub fn reset(&mut self) {
+        for parser in self.parsers.iter_mut() {
+            parser.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,37 @@
+//! Unit tests
+
+use super::*;
+
+/// SysEx messages of two cables interleaved packet by packet.
+#[test]
+fn interleaved_sysex() {
+    let mut parser = UsbMidiParser::<2, 16>::new();
+
+    let packets = [
+        [0x09, 0x90, 60, 100],
+        [0x04, 0xF0, 0x41, 0x10],
+        [0x14, 0xF0, 0x43, 0x20],
+        [0x04, 0x11, 0x12, 0x13],
+        [0x1F, 0xF8, 0, 0],
+        [0x16, 0x21, 0xF7, 0],
+        [0x05, 0xF7, 0, 0],
+        [0x29, 0x90, 60, 100],
+    ];
+
+    let mut messages = Vec::new();
+    for packet in packets {
+        if let Some((cable, message)) = parser.parse_packet(packet).unwrap() {
+            messages.push((cable, message.to_vec()));
+        }
+    }
+
+    assert_eq!(
+        messages,
+        [
+            (0, vec![0x90, 60, 100]),
+            (1, vec![0xF8]),
+            (1, vec![0xF0, 0x43, 0x20, 0x21, 0xF7]),
+            (0, vec![0xF0, 0x41, 0x10, 0x11, 0x12, 0x13, 0xF7]),
+        ]
+    );
+}